@@ -0,0 +1,161 @@
+use crate::i18n::t;
+use crate::models::Task;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Bucket {
+    Overdue,
+    Today,
+    Tomorrow,
+    ThisWeek,
+    NextWeek,
+    Later,
+    NoDate,
+}
+
+impl fmt::Display for Bucket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let key = match self {
+            Bucket::Overdue => "agenda_overdue",
+            Bucket::Today => "agenda_today",
+            Bucket::Tomorrow => "agenda_tomorrow",
+            Bucket::ThisWeek => "agenda_this_week",
+            Bucket::NextWeek => "agenda_next_week",
+            Bucket::Later => "agenda_later",
+            Bucket::NoDate => "agenda_no_date",
+        };
+        write!(f, "{}", t(key))
+    }
+}
+
+impl Bucket {
+    // Stable snake_case key used in machine-readable output.
+    pub fn key(self) -> &'static str {
+        match self {
+            Bucket::Overdue => "overdue",
+            Bucket::Today => "today",
+            Bucket::Tomorrow => "tomorrow",
+            Bucket::ThisWeek => "this_week",
+            Bucket::NextWeek => "next_week",
+            Bucket::Later => "later",
+            Bucket::NoDate => "no_date",
+        }
+    }
+}
+
+// Weeks run Monday through Sunday.
+pub fn bucket_for(due: Option<NaiveDate>, today: NaiveDate) -> Bucket {
+    let Some(due) = due else {
+        return Bucket::NoDate;
+    };
+    let end_of_week = today + Duration::days(6 - today.weekday().num_days_from_monday() as i64);
+    let end_of_next_week = end_of_week + Duration::days(7);
+
+    if due < today {
+        Bucket::Overdue
+    } else if due == today {
+        Bucket::Today
+    } else if due == today + Duration::days(1) {
+        Bucket::Tomorrow
+    } else if due <= end_of_week {
+        Bucket::ThisWeek
+    } else if due <= end_of_next_week {
+        Bucket::NextWeek
+    } else {
+        Bucket::Later
+    }
+}
+
+// Returns only the non-empty buckets, in chronological order. Tasks within a
+// bucket are ordered by priority, then by due date.
+pub fn build_agenda(tasks: &[Task], today: NaiveDate) -> Vec<(Bucket, Vec<&Task>)> {
+    let mut entries: Vec<(Bucket, &Task)> = tasks
+        .iter()
+        .map(|task| (bucket_for(task.due(), today), task))
+        .collect();
+    entries.sort_by(|(a_bucket, a), (b_bucket, b)| {
+        a_bucket
+            .cmp(b_bucket)
+            .then_with(|| a.priority.cmp(&b.priority))
+            .then_with(|| a.due().cmp(&b.due()))
+    });
+
+    let mut agenda: Vec<(Bucket, Vec<&Task>)> = Vec::new();
+    for (bucket, task) in entries {
+        match agenda.last_mut() {
+            Some((last, group)) if *last == bucket => group.push(task),
+            _ => agenda.push((bucket, vec![task])),
+        }
+    }
+    agenda
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+
+    // Wednesday
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, 14).unwrap()
+    }
+
+    fn date(day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(2026, 1, day)
+    }
+
+    fn task(id: &str, due_date: &str, priority: Priority) -> Task {
+        Task {
+            id: id.to_string(),
+            description: format!("Task {id}"),
+            due_date: due_date.to_string(),
+            priority,
+            notes: "".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn buckets_relative_to_today() {
+        assert_eq!(bucket_for(date(13), today()), Bucket::Overdue);
+        assert_eq!(bucket_for(date(14), today()), Bucket::Today);
+        assert_eq!(bucket_for(date(15), today()), Bucket::Tomorrow);
+        assert_eq!(bucket_for(date(18), today()), Bucket::ThisWeek);
+        assert_eq!(bucket_for(date(19), today()), Bucket::NextWeek);
+        assert_eq!(bucket_for(date(25), today()), Bucket::NextWeek);
+        assert_eq!(bucket_for(date(26), today()), Bucket::Later);
+        assert_eq!(bucket_for(None, today()), Bucket::NoDate);
+    }
+
+    #[test]
+    fn tomorrow_wins_over_next_week_on_sunday() {
+        let sunday = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        assert_eq!(bucket_for(date(19), sunday), Bucket::Tomorrow);
+        assert_eq!(bucket_for(date(20), sunday), Bucket::NextWeek);
+    }
+
+    #[test]
+    fn agenda_groups_and_orders_by_priority() {
+        let tasks = vec![
+            task("01", "01/20/2026", Priority::Minor),
+            task("02", "01/10/2026", Priority::None),
+            task("03", "01/12/2026", Priority::Asap),
+            task("04", "", Priority::Medium),
+            task("05", "01/21/2026", Priority::Asap),
+        ];
+        let agenda = build_agenda(&tasks, today());
+        let summary: Vec<(Bucket, Vec<&str>)> = agenda
+            .iter()
+            .map(|(bucket, tasks)| (*bucket, tasks.iter().map(|t| t.id.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Bucket::Overdue, vec!["03", "02"]),
+                (Bucket::NextWeek, vec!["05", "01"]),
+                (Bucket::NoDate, vec!["04"]),
+            ]
+        );
+    }
+}
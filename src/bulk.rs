@@ -0,0 +1,212 @@
+use crate::dates::DatePattern;
+use crate::filter::Filter;
+use crate::i18n::{t, tf};
+use crate::models::{Action, Priority, Task};
+use crate::reminders;
+use chrono::NaiveDate;
+
+// A single `--set` assignment such as `priority=none` or `tags+=urgent`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum FieldUpdate {
+    Description(String),
+    DueDate(String),
+    Priority(Priority),
+    Notes(String),
+    Remind(String),
+    Tags(Vec<String>),
+    AddTags(Vec<String>),
+    RemoveTags(Vec<String>),
+}
+
+impl FieldUpdate {
+    // Values go through the same validation as the interactive prompts. Due
+    // dates are read in the `dates` layout.
+    pub fn parse(assignment: &str, dates: &DatePattern) -> Result<FieldUpdate, String> {
+        let (field, value, op) = if let Some((field, value)) = assignment.split_once("+=") {
+            (field, value, '+')
+        } else if let Some((field, value)) = assignment.split_once("-=") {
+            (field, value, '-')
+        } else if let Some((field, value)) = assignment.split_once('=') {
+            (field, value, '=')
+        } else {
+            return Err(tf("invalid_set", &[("assignment", &assignment)]));
+        };
+
+        let field = field.trim().to_lowercase();
+        let value = value.trim();
+        match (field.as_str(), op) {
+            ("description", '=') if value.is_empty() => Err(t("description_empty").to_string()),
+            ("description", '=') => Ok(FieldUpdate::Description(value.to_string())),
+            ("due" | "due_date", '=') if value.is_empty() => {
+                Ok(FieldUpdate::DueDate(String::new()))
+            }
+            ("due" | "due_date", '=') => {
+                let date = dates.parse_input(value).map_err(|()| {
+                    tf(
+                        "invalid_date_value",
+                        &[("input", &value), ("format", dates)],
+                    )
+                })?;
+                Ok(FieldUpdate::DueDate(date))
+            }
+            ("priority", '=') => Priority::from_name(value)
+                .map(FieldUpdate::Priority)
+                .ok_or_else(|| tf("invalid_priority", &[("value", &value)])),
+            ("notes", '=') => Ok(FieldUpdate::Notes(value.to_string())),
+            ("remind", '=') => reminders::parse(value, dates).map(FieldUpdate::Remind),
+            ("tags" | "tag", '=') => Ok(FieldUpdate::Tags(Task::parse_tags(value))),
+            ("tags" | "tag", '+') => Ok(FieldUpdate::AddTags(Task::parse_tags(value))),
+            ("tags" | "tag", '-') => Ok(FieldUpdate::RemoveTags(Task::parse_tags(value))),
+            (other, '=') => Err(tf("unknown_set_field", &[("field", &other)])),
+            (other, _) => Err(tf("set_field_no_add", &[("field", &other)])),
+        }
+    }
+
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            FieldUpdate::Description(_) => "description",
+            FieldUpdate::DueDate(_) => "due_date",
+            FieldUpdate::Priority(_) => "priority",
+            FieldUpdate::Notes(_) => "notes",
+            FieldUpdate::Remind(_) => "remind",
+            FieldUpdate::Tags(_) | FieldUpdate::AddTags(_) | FieldUpdate::RemoveTags(_) => "tags",
+        }
+    }
+
+    // Returns whether the task actually changed.
+    pub fn apply(&self, task: &mut Task) -> bool {
+        let before = task.clone();
+        match self {
+            FieldUpdate::Description(v) => task.description = v.clone(),
+            FieldUpdate::DueDate(v) => task.due_date = v.clone(),
+            FieldUpdate::Priority(v) => task.priority = v.clone(),
+            FieldUpdate::Notes(v) => task.notes = v.clone(),
+            FieldUpdate::Remind(v) => task.remind = v.clone(),
+            FieldUpdate::Tags(v) => task.tags = v.clone(),
+            FieldUpdate::AddTags(v) => {
+                for tag in v {
+                    if !task.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                        task.tags.push(tag.clone());
+                    }
+                }
+            }
+            FieldUpdate::RemoveTags(v) => {
+                task.tags
+                    .retain(|t| !v.iter().any(|r| r.eq_ignore_ascii_case(t)));
+            }
+        }
+        *task != before
+    }
+}
+
+// Applies every update and records a single history entry naming the fields
+// that changed. Returns whether anything changed.
+pub fn apply_updates(task: &mut Task, updates: &[FieldUpdate]) -> bool {
+    let mut changed: Vec<&str> = Vec::new();
+    for update in updates {
+        if update.apply(task) && !changed.contains(&update.field_name()) {
+            changed.push(update.field_name());
+        }
+    }
+    if !changed.is_empty() {
+        task.record(Action::Edited, changed.join(","));
+    }
+    !changed.is_empty()
+}
+
+// Resolves the tasks a bulk command acts on: the explicitly listed IDs plus
+// every task matching the filter, without duplicates and in list order.
+pub fn select_targets(
+    tasks: &[Task],
+    ids: &[String],
+    filter: Option<&Filter>,
+    today: NaiveDate,
+) -> Result<Vec<String>, String> {
+    let wanted: Vec<String> = ids.iter().map(|id| id.to_uppercase()).collect();
+    if let Some(missing) = wanted.iter().find(|id| !tasks.iter().any(|t| &t.id == *id)) {
+        return Err(tf("task_not_found", &[("id", &missing)]));
+    }
+    Ok(tasks
+        .iter()
+        .filter(|t| wanted.contains(&t.id) || filter.is_some_and(|f| f.matches(t, today)))
+        .map(|t| t.id.clone())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, tags: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            description: format!("Task {id}"),
+            due_date: "10/20/2026".to_string(),
+            priority: Priority::Minor,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+    }
+
+    fn parse(assignment: &str) -> Result<FieldUpdate, String> {
+        FieldUpdate::parse(assignment, &DatePattern::default())
+    }
+
+    #[test]
+    fn parse_assignments() {
+        assert_eq!(
+            parse("priority=none"),
+            Ok(FieldUpdate::Priority(Priority::None))
+        );
+        assert_eq!(
+            parse("due=1/5/26"),
+            Ok(FieldUpdate::DueDate("2026-01-05".to_string()))
+        );
+        assert_eq!(
+            parse("tags+=a, b"),
+            Ok(FieldUpdate::AddTags(vec!["a".to_string(), "b".to_string()]))
+        );
+        assert_eq!(parse("notes="), Ok(FieldUpdate::Notes(String::new())));
+        assert_eq!(parse("due="), Ok(FieldUpdate::DueDate(String::new())));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_values() {
+        assert!(parse("priority=urgent").is_err());
+        assert!(parse("due=13/01/2026").is_err());
+        assert!(parse("description=").is_err());
+        assert!(parse("colour=red").is_err());
+        assert!(parse("notes+=more").is_err());
+        assert!(parse("priority").is_err());
+    }
+
+    #[test]
+    fn apply_reports_changes() {
+        let mut t = task("1A", &["ops"]);
+        assert!(parse("tags+=OPS,new").unwrap().apply(&mut t));
+        assert_eq!(t.tags, vec!["ops", "new"]);
+        assert!(parse("tags-=Ops").unwrap().apply(&mut t));
+        assert_eq!(t.tags, vec!["new"]);
+        assert!(!parse("priority=minor").unwrap().apply(&mut t));
+    }
+
+    #[test]
+    fn select_targets_combines_ids_and_filter() {
+        let tasks = vec![
+            task("1A", &[]),
+            task("2B", &["sprint12"]),
+            task("3C", &["sprint12"]),
+        ];
+        let filter = Filter::parse("tag:sprint12", &DatePattern::default()).unwrap();
+        let ids = vec!["1a".to_string(), "2B".to_string()];
+        assert_eq!(
+            select_targets(&tasks, &ids, Some(&filter), today()),
+            Ok(vec!["1A".to_string(), "2B".to_string(), "3C".to_string()])
+        );
+        assert!(select_targets(&tasks, &["ZZ".to_string()], None, today()).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+use crate::i18n::{t, tf};
+use crate::models::Task;
+use crate::render::Renderer;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::BTreeMap;
+
+const WEEKDAYS: [&str; 7] = [
+    "weekday_mon",
+    "weekday_tue",
+    "weekday_wed",
+    "weekday_thu",
+    "weekday_fri",
+    "weekday_sat",
+    "weekday_sun",
+];
+const MONTHS: [&str; 12] = [
+    "month_january",
+    "month_february",
+    "month_march",
+    "month_april",
+    "month_may",
+    "month_june",
+    "month_july",
+    "month_august",
+    "month_september",
+    "month_october",
+    "month_november",
+    "month_december",
+];
+
+// Below these cell widths the grid is unreadable and we fall back to a list.
+const MIN_MONTH_CELL: usize = 4;
+const MIN_WEEK_CELL: usize = 8;
+
+pub fn terminal_width() -> usize {
+    crossterm::terminal::size()
+        .map(|(cols, _)| cols as usize)
+        .ok()
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(80)
+}
+
+// Accepts MM/YYYY, M/YYYY, or the same with `-` or `.` as the delimiter.
+pub fn parse_month(input: &str) -> Result<(i32, u32), String> {
+    let normalized = input.trim().replace(['-', '.'], "/");
+    let error = || tf("invalid_month", &[("input", &input)]);
+    let (month, year) = normalized.split_once('/').ok_or_else(error)?;
+    let month: u32 = month.parse().map_err(|_| error())?;
+    let year: i32 = year.parse().map_err(|_| error())?;
+    if year.to_string().len() != 4 || !(1..=12).contains(&month) {
+        return Err(error());
+    }
+    Ok((year, month))
+}
+
+pub fn month_title(year: i32, month: u32) -> String {
+    format!("{} {}", t(MONTHS[month as usize - 1]), year)
+}
+
+pub fn tasks_by_day(tasks: &[Task]) -> BTreeMap<NaiveDate, Vec<&Task>> {
+    let mut days: BTreeMap<NaiveDate, Vec<&Task>> = BTreeMap::new();
+    for task in tasks {
+        if let Some(due) = task.due() {
+            days.entry(due).or_default().push(task);
+        }
+    }
+    for day_tasks in days.values_mut() {
+        day_tasks.sort_by(|a, b| a.priority.cmp(&b.priority));
+    }
+    days
+}
+
+pub fn month_days(year: i32, month: u32) -> Vec<NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).expect("month validated by parse_month");
+    first
+        .iter_days()
+        .take_while(|d| d.month() == month)
+        .collect()
+}
+
+pub fn week_days(date: NaiveDate) -> Vec<NaiveDate> {
+    let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+    monday.iter_days().take(7).collect()
+}
+
+fn fit(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count <= width {
+        format!("{text:<width$}")
+    } else if width == 0 {
+        String::new()
+    } else {
+        let truncated: String = text.chars().take(width - 1).collect();
+        format!("{truncated}…")
+    }
+}
+
+fn day_style(renderer: &Renderer, day: NaiveDate, has_tasks: bool) -> &str {
+    if day == renderer.today {
+        &renderer.theme.due_today
+    } else if has_tasks && day < renderer.today {
+        &renderer.theme.overdue
+    } else {
+        ""
+    }
+}
+
+// Short IDs when they fit in the cell, otherwise a count.
+fn cell_summary(tasks: &[&Task], width: usize) -> String {
+    if tasks.is_empty() {
+        return String::new();
+    }
+    let ids = tasks
+        .iter()
+        .map(|t| t.id.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if ids.chars().count() <= width {
+        ids
+    } else {
+        format!("({})", tasks.len())
+    }
+}
+
+fn render_list(
+    days: &[NaiveDate],
+    by_day: &BTreeMap<NaiveDate, Vec<&Task>>,
+    renderer: &Renderer,
+) -> String {
+    let mut lines = Vec::new();
+    for day in days {
+        let tasks = by_day.get(day).map(Vec::as_slice).unwrap_or(&[]);
+        if tasks.is_empty() && *day != renderer.today {
+            continue;
+        }
+        let label = format!(
+            "{} {}",
+            t(WEEKDAYS[day.weekday().num_days_from_monday() as usize]),
+            renderer.date_format.format_day_month(*day)
+        );
+        lines.push(renderer.paint(day_style(renderer, *day, !tasks.is_empty()), &label));
+        for task in tasks {
+            lines.push(format!("  {}  {}", task.id, task.description));
+        }
+    }
+    lines.join("\n")
+}
+
+pub fn render_month(
+    tasks: &[Task],
+    year: i32,
+    month: u32,
+    width: usize,
+    renderer: &Renderer,
+) -> String {
+    let days = month_days(year, month);
+    let by_day = tasks_by_day(tasks);
+    let title = month_title(year, month);
+
+    // Seven cells separated by single spaces.
+    let cell = width.saturating_sub(6) / 7;
+    if cell < MIN_MONTH_CELL {
+        return format!("{title}\n{}", render_list(&days, &by_day, renderer));
+    }
+    let cell = cell.min(12);
+    let grid_width = cell * 7 + 6;
+
+    let mut lines = vec![format!("{title:^grid_width$}").trim_end().to_string()];
+    lines.push(
+        WEEKDAYS
+            .iter()
+            .map(|d| fit(t(d), cell))
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end()
+            .to_string(),
+    );
+
+    let lead = days[0].weekday().num_days_from_monday() as usize;
+    let mut slots: Vec<Option<NaiveDate>> = vec![None; lead];
+    slots.extend(days.iter().copied().map(Some));
+    while !slots.len().is_multiple_of(7) {
+        slots.push(None);
+    }
+
+    for week in slots.chunks(7) {
+        let mut numbers = Vec::new();
+        let mut summaries = Vec::new();
+        for slot in week {
+            match slot {
+                Some(day) => {
+                    let tasks = by_day.get(day).map(Vec::as_slice).unwrap_or(&[]);
+                    let style = day_style(renderer, *day, !tasks.is_empty());
+                    numbers.push(renderer.paint(style, &fit(&day.day().to_string(), cell)));
+                    summaries.push(fit(&cell_summary(tasks, cell), cell));
+                }
+                None => {
+                    numbers.push(" ".repeat(cell));
+                    summaries.push(" ".repeat(cell));
+                }
+            }
+        }
+        lines.push(numbers.join(" ").trim_end().to_string());
+        lines.push(summaries.join(" ").trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+pub fn render_week(tasks: &[Task], date: NaiveDate, width: usize, renderer: &Renderer) -> String {
+    let days = week_days(date);
+    let by_day = tasks_by_day(tasks);
+
+    let cell = width.saturating_sub(6) / 7;
+    if cell < MIN_WEEK_CELL {
+        return render_list(&days, &by_day, renderer);
+    }
+    let cell = cell.min(24);
+
+    let columns: Vec<Vec<String>> = days
+        .iter()
+        .map(|day| {
+            by_day
+                .get(day)
+                .map(|tasks| {
+                    tasks
+                        .iter()
+                        .map(|t| format!("{} {}", t.id, t.description))
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+        .collect();
+    let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut lines = Vec::new();
+    lines.push(
+        days.iter()
+            .map(|day| {
+                let label = format!(
+                    "{} {}",
+                    t(WEEKDAYS[day.weekday().num_days_from_monday() as usize]),
+                    renderer.date_format.format_day_month(*day)
+                );
+                let has_tasks = by_day.contains_key(day);
+                renderer.paint(day_style(renderer, *day, has_tasks), &fit(&label, cell))
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    lines.push(vec!["─".repeat(cell); 7].join(" "));
+    for row in 0..rows {
+        let line = columns
+            .iter()
+            .map(|column| fit(column.get(row).map(String::as_str).unwrap_or(""), cell))
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+    use crate::render::Theme;
+
+    fn task(id: &str, due_date: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            description: format!("Task {id}"),
+            due_date: due_date.to_string(),
+            priority: Priority::None,
+            notes: "".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn renderer() -> Renderer {
+        Renderer {
+            enabled: false,
+            theme: Theme::default(),
+            today: NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_month_formats() {
+        assert_eq!(parse_month("10/2026"), Ok((2026, 10)));
+        assert_eq!(parse_month("1-2026"), Ok((2026, 1)));
+        assert!(parse_month("13/2026").is_err());
+        assert!(parse_month("10/26").is_err());
+        assert!(parse_month("October").is_err());
+    }
+
+    #[test]
+    fn month_days_handles_leap_years() {
+        assert_eq!(month_days(2028, 2).len(), 29);
+        assert_eq!(month_days(2026, 2).len(), 28);
+    }
+
+    #[test]
+    fn month_grid_places_ids_on_due_day() {
+        let tasks = vec![task("1A", "10/01/2026"), task("2B", "10/01/2026")];
+        let output = render_month(&tasks, 2026, 10, 80, &renderer());
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0].trim(), "October 2026");
+        assert!(lines[1].starts_with("Mon"));
+        // October 1st 2026 is a Thursday, the fourth column.
+        assert!(lines[2].split_whitespace().next() == Some("1"));
+        assert!(lines[3].contains("1A 2B"));
+    }
+
+    #[test]
+    fn month_grid_falls_back_to_counts() {
+        let tasks = vec![
+            task("1A", "10/01/2026"),
+            task("2B", "10/01/2026"),
+            task("3C", "10/01/2026"),
+        ];
+        let output = render_month(&tasks, 2026, 10, 50, &renderer());
+        assert!(output.contains("(3)"));
+    }
+
+    #[test]
+    fn narrow_terminal_uses_list_layout() {
+        let tasks = vec![task("1A", "10/01/2026")];
+        let output = render_month(&tasks, 2026, 10, 30, &renderer());
+        assert_eq!(output, "October 2026\nThu 10/01\n  1A  Task 1A\nMon 10/19");
+    }
+
+    #[test]
+    fn week_view_lists_tasks_per_column() {
+        let tasks = vec![task("1A", "10/19/2026"), task("2B", "10/25/2026")];
+        let date = NaiveDate::from_ymd_opt(2026, 10, 21).unwrap();
+        let output = render_week(&tasks, date, 120, &renderer());
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("Mon 10/19"));
+        assert!(lines[2].starts_with("1A Task 1A"));
+        assert!(lines[2].ends_with("2B Task 2B"));
+    }
+
+    #[test]
+    fn day_labels_follow_the_date_format() {
+        let renderer = Renderer {
+            date_format: "DD.MM.YYYY".parse().unwrap(),
+            ..renderer()
+        };
+        let tasks = vec![task("1A", "10/01/2026")];
+        let output = render_month(&tasks, 2026, 10, 30, &renderer);
+        assert!(output.contains("Thu 01.10\n"));
+        let date = NaiveDate::from_ymd_opt(2026, 10, 21).unwrap();
+        assert!(render_week(&tasks, date, 120, &renderer).starts_with("Mon 19.10"));
+    }
+}
@@ -0,0 +1,273 @@
+use crate::i18n::{t, tf};
+
+// Built-in commands. Aliases and reports from the config file cannot reuse
+// these names.
+pub const COMMANDS: &[&str] = &[
+    "new",
+    "clone",
+    "view",
+    "show",
+    "next",
+    "explain",
+    "agenda",
+    "calendar",
+    "stats",
+    "done",
+    "edit",
+    "snooze",
+    "reschedule",
+    "move",
+    "remind",
+    "notify",
+    "schedule",
+    "delete",
+    "trash",
+    "template",
+    "tui",
+    "shell",
+    "config",
+    "help",
+];
+
+// Options that consume a value, either as `--name value` or `--name=value`.
+// Every other `--name` is treated as a boolean flag.
+const VALUE_OPTIONS: &[&str] = &[
+    "format",
+    "color",
+    "theme",
+    "weeks",
+    "filter",
+    "set",
+    "older-than",
+    "sort",
+    "shift",
+    "template",
+    "due",
+    "before",
+    "after",
+    "file",
+];
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Args {
+    pub command: Option<String>,
+    pub positionals: Vec<String>,
+    options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    pub fn parse(raw: &[String]) -> Args {
+        let mut args = Args::default();
+        let mut iter = raw.iter();
+
+        while let Some(arg) = iter.next() {
+            if let Some(option) = arg.strip_prefix("--") {
+                if let Some((name, value)) = option.split_once('=') {
+                    args.options
+                        .push((name.to_string(), Some(value.to_string())));
+                } else if VALUE_OPTIONS.contains(&option) {
+                    args.options
+                        .push((option.to_string(), iter.next().cloned()));
+                } else {
+                    args.options.push((option.to_string(), None));
+                }
+            } else if args.command.is_none() {
+                args.command = Some(arg.clone());
+            } else {
+                args.positionals.push(arg.clone());
+            }
+        }
+
+        args
+    }
+
+    // Replaces the command with an alias expansion. The expansion's own
+    // arguments come first, so options given on the command line win.
+    pub fn expand(self, expansion: &[String]) -> Args {
+        let mut args = Args::parse(expansion);
+        args.positionals.extend(self.positionals);
+        args.options.extend(self.options);
+        args
+    }
+
+    pub fn positional(&self, index: usize) -> Option<&str> {
+        self.positionals.get(index).map(String::as_str)
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(n, _)| n == name)
+    }
+
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.options
+            .iter()
+            .filter(|(n, _)| n == name)
+            .filter_map(|(_, v)| v.as_deref())
+            .collect()
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+}
+
+// The words after the command, as typed. External plugins parse their own
+// arguments, so nothing after the command is interpreted here.
+pub fn words_after_command(raw: &[String]) -> &[String] {
+    let mut iter = raw.iter().enumerate();
+    while let Some((index, arg)) = iter.next() {
+        match arg.strip_prefix("--") {
+            Some(option) if VALUE_OPTIONS.contains(&option) => {
+                iter.next();
+            }
+            Some(_) => {}
+            None => return &raw[index + 1..],
+        }
+    }
+    &[]
+}
+
+// Splits a line typed in the shell into words the way a POSIX shell would for
+// simple cases: whitespace separates words, and single or double quotes group
+// them. A backslash escapes the next character outside single quotes.
+pub fn split_line(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => word.push(c),
+            (_, '\\') => {
+                word.push(chars.next().ok_or(t("unfinished_escape"))?);
+                in_word = true;
+            }
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if let Some(q) = quote {
+        return Err(tf("missing_quote", &[("quote", &q)]));
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Args {
+        let raw: Vec<String> = line.split_whitespace().map(String::from).collect();
+        Args::parse(&raw)
+    }
+
+    #[test]
+    fn parse_command_and_positionals() {
+        let args = parse("done 1A");
+        assert_eq!(args.command.as_deref(), Some("done"));
+        assert_eq!(args.positional(0), Some("1A"));
+        assert_eq!(args.positional(1), None);
+    }
+
+    #[test]
+    fn expand_alias_keeps_command_line_arguments() {
+        let expansion: Vec<String> = ["view", "due:today", "--sort", "priority"]
+            .map(String::from)
+            .to_vec();
+        let args = parse("today tag:work --sort due --format json").expand(&expansion);
+        assert_eq!(args.command.as_deref(), Some("view"));
+        assert_eq!(args.positionals, vec!["due:today", "tag:work"]);
+        assert_eq!(args.value("sort"), Some("due"));
+        assert_eq!(args.value("format"), Some("json"));
+    }
+
+    #[test]
+    fn parse_value_option_both_forms() {
+        assert_eq!(parse("view --format json").value("format"), Some("json"));
+        assert_eq!(parse("view --format=tsv").value("format"), Some("tsv"));
+    }
+
+    #[test]
+    fn parse_options_before_command() {
+        let args = parse("--format json view");
+        assert_eq!(args.command.as_deref(), Some("view"));
+        assert_eq!(args.value("format"), Some("json"));
+    }
+
+    #[test]
+    fn parse_boolean_flag_does_not_consume_positional() {
+        let args = parse("done --verbose 1A");
+        assert!(args.flag("verbose"));
+        assert_eq!(args.value("verbose"), None);
+        assert_eq!(args.positional(0), Some("1A"));
+    }
+
+    #[test]
+    fn words_after_command_are_kept_as_typed() {
+        let raw: Vec<String> = "--format json sync --dry-run push"
+            .split(' ')
+            .map(String::from)
+            .collect();
+        assert_eq!(words_after_command(&raw), ["--dry-run", "push"]);
+        assert!(words_after_command(&raw[..2]).is_empty());
+    }
+
+    #[test]
+    fn repeated_values_are_collected() {
+        let args = parse("edit 1A --set priority=none --set=notes=hi");
+        assert_eq!(args.values("set"), vec!["priority=none", "notes=hi"]);
+    }
+
+    #[test]
+    fn last_value_wins() {
+        assert_eq!(
+            parse("view --format json --format tsv").value("format"),
+            Some("tsv")
+        );
+    }
+
+    #[test]
+    fn split_line_handles_quotes() {
+        assert_eq!(
+            split_line(r#"edit 1A --set "description=Buy milk" --set "notes=it's" 'a\b'"#),
+            Ok(vec![
+                "edit".to_string(),
+                "1A".to_string(),
+                "--set".to_string(),
+                "description=Buy milk".to_string(),
+                "--set".to_string(),
+                "notes=it's".to_string(),
+                "a\\b".to_string(),
+            ])
+        );
+        assert_eq!(
+            split_line(r#"view --filter "" x\ y"#).unwrap()[2..],
+            ["", "x y"]
+        );
+        assert!(split_line("view \"open").is_err());
+    }
+}
@@ -0,0 +1,874 @@
+use crate::cli::{split_line, Args, COMMANDS};
+use crate::dates::DatePattern;
+use crate::filter::{Filter, SortKey};
+use crate::hooks::Hooks;
+use crate::i18n::{create_error, read_error, t, tf, write_error, Language};
+use crate::models::Priority;
+use crate::render::{ColorChoice, Theme};
+use crate::report::Report;
+use crate::task_manager::IdStrategy;
+use crate::urgency::Weights;
+use chrono::NaiveTime;
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml_edit::{value, DocumentMut, Item};
+
+const LOCAL_FILE: &str = ".todo_cli.toml";
+
+// How a value is written back to the file by `config set`.
+#[derive(PartialEq)]
+enum Kind {
+    Text,
+    Integer,
+    Number,
+}
+
+struct Setting {
+    key: &'static str,
+    default: &'static str,
+    check: fn(&str) -> Result<(), String>,
+    kind: Kind,
+}
+
+fn check_priority(v: &str) -> Result<(), String> {
+    Priority::from_name(v)
+        .map(|_| ())
+        .ok_or_else(|| tf("invalid_priority", &[("value", &v)]))
+}
+
+fn check_id_strategy(v: &str) -> Result<(), String> {
+    v.parse::<IdStrategy>().map(|_| ())
+}
+
+fn check_date_format(v: &str) -> Result<(), String> {
+    v.parse::<DatePattern>().map(|_| ())
+}
+
+fn check_path(v: &str) -> Result<(), String> {
+    if v.trim().is_empty() {
+        Err(t("database_path_empty").to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn check_color(v: &str) -> Result<(), String> {
+    v.parse::<ColorChoice>().map(|_| ())
+}
+
+fn check_theme(v: &str) -> Result<(), String> {
+    Theme::parse(v).map(|_| ())
+}
+
+fn check_language(v: &str) -> Result<(), String> {
+    if v == "auto" {
+        Ok(())
+    } else {
+        v.parse::<Language>().map(|_| ())
+    }
+}
+
+fn check_width(v: &str) -> Result<(), String> {
+    match v.parse::<usize>() {
+        Ok(1..=200) => Ok(()),
+        _ => Err(tf("invalid_separator_width", &[("value", &v)])),
+    }
+}
+
+fn check_weight(v: &str) -> Result<(), String> {
+    match v.parse::<f64>() {
+        Ok(weight) if weight.is_finite() => Ok(()),
+        _ => Err(tf("invalid_urgency_weight", &[("value", &v)])),
+    }
+}
+
+fn check_timeout(v: &str) -> Result<(), String> {
+    match v.parse::<u64>() {
+        Ok(1..=3600) => Ok(()),
+        _ => Err(tf("invalid_hook_timeout", &[("value", &v)])),
+    }
+}
+
+fn check_time(v: &str) -> Result<(), String> {
+    NaiveTime::parse_from_str(v, "%H:%M")
+        .map(|_| ())
+        .map_err(|_| tf("invalid_time", &[("value", &v)]))
+}
+
+fn check_interval(v: &str) -> Result<(), String> {
+    match v.parse::<u64>() {
+        Ok(1..=3600) => Ok(()),
+        _ => Err(tf("invalid_interval", &[("value", &v)])),
+    }
+}
+
+fn check_command(v: &str) -> Result<(), String> {
+    if v.trim().is_empty() {
+        Err(t("reminder_command_blank").to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn check_filter(v: &str) -> Result<(), String> {
+    Filter::check(v)
+}
+
+fn check_sort(v: &str) -> Result<(), String> {
+    if v == "none" {
+        Ok(())
+    } else {
+        v.parse::<SortKey>().map(|_| ())
+    }
+}
+
+const SETTINGS: &[Setting] = &[
+    Setting {
+        key: "tasks.default_priority",
+        default: "none",
+        check: check_priority,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "tasks.id_strategy",
+        default: "random",
+        check: check_id_strategy,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "dates.input_format",
+        default: "MM/DD/YYYY",
+        check: check_date_format,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "dates.output_format",
+        default: "MM/DD/YYYY",
+        check: check_date_format,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "storage.database",
+        default: "task_database.xml",
+        check: check_path,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "display.color",
+        default: "auto",
+        check: check_color,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "display.theme",
+        default: "default",
+        check: check_theme,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "display.language",
+        default: "auto",
+        check: check_language,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "display.separator_width",
+        default: "40",
+        check: check_width,
+        kind: Kind::Integer,
+    },
+    Setting {
+        key: "view.filter",
+        default: "",
+        check: check_filter,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "view.sort",
+        default: "none",
+        check: check_sort,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "urgency.priority",
+        default: "6",
+        check: check_weight,
+        kind: Kind::Number,
+    },
+    Setting {
+        key: "urgency.due",
+        default: "12",
+        check: check_weight,
+        kind: Kind::Number,
+    },
+    Setting {
+        key: "urgency.age",
+        default: "2",
+        check: check_weight,
+        kind: Kind::Number,
+    },
+    Setting {
+        key: "urgency.tags",
+        default: "1",
+        check: check_weight,
+        kind: Kind::Number,
+    },
+    Setting {
+        key: "hooks.timeout",
+        default: "10",
+        check: check_timeout,
+        kind: Kind::Integer,
+    },
+    Setting {
+        key: "reminders.command",
+        default: "auto",
+        check: check_command,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "reminders.due_time",
+        default: "09:00",
+        check: check_time,
+        kind: Kind::Text,
+    },
+    Setting {
+        key: "reminders.interval",
+        default: "60",
+        check: check_interval,
+        kind: Kind::Integer,
+    },
+];
+
+fn setting(key: &str) -> Result<&'static Setting, String> {
+    SETTINGS
+        .iter()
+        .find(|s| s.key == key)
+        .ok_or_else(|| tf("unknown_setting", &[("key", &key)]))
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Entry {
+    pub key: &'static str,
+    pub value: String,
+    pub source: String,
+}
+
+// Settings come from the built-in defaults, then the user's config file, then
+// `.todo_cli.toml` in the current directory. Later files win, and command-line
+// options win over all of them. The `[aliases]` and `[reports.NAME]` tables
+// add commands of their own, and `[urgency.tag]` weights tags for urgency.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Config {
+    values: Vec<(String, Source)>,
+    aliases: Vec<(String, String)>,
+    reports: Vec<Report>,
+    tag_weights: Vec<(String, f64)>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            values: SETTINGS
+                .iter()
+                .map(|s| (s.default.to_string(), Source::Default))
+                .collect(),
+            aliases: Vec::new(),
+            reports: Vec::new(),
+            tag_weights: Vec::new(),
+        }
+    }
+}
+
+fn check_command_name(name: &str) -> Result<(), String> {
+    if COMMANDS.contains(&name) {
+        Err(tf("builtin_command_name", &[("name", &name)]))
+    } else if name.is_empty() || name.starts_with('-') || name.contains(char::is_whitespace) {
+        Err(tf("invalid_command_name", &[("name", &name)]))
+    } else {
+        Ok(())
+    }
+}
+
+// Accepts a string, or for `columns` also an array of strings.
+fn report_field(item: &Item) -> Option<String> {
+    let value = item.as_value()?;
+    if let Some(array) = value.as_array() {
+        let parts: Option<Vec<&str>> = array.iter().map(|v| v.as_str()).collect();
+        return parts.map(|p| p.join(","));
+    }
+    value.as_str().map(str::to_string)
+}
+
+pub fn keys() -> impl Iterator<Item = &'static str> {
+    SETTINGS.iter().map(|s| s.key)
+}
+
+// `$XDG_CONFIG_HOME/todo_cli`, falling back to `~/.config/todo_cli`.
+fn config_home() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+fn global_dir() -> Option<PathBuf> {
+    config_home().map(|base| base.join("todo_cli"))
+}
+
+// Where `schedule export --systemd` puts user units by default.
+pub fn systemd_user_dir() -> Option<PathBuf> {
+    config_home().map(|base| base.join("systemd").join("user"))
+}
+
+pub fn global_path() -> Option<PathBuf> {
+    global_dir().map(|dir| dir.join("config.toml"))
+}
+
+// Hooks only ever come from the user's own config directory, never from a
+// project's local config file.
+pub fn hooks_dir() -> Option<PathBuf> {
+    global_dir().map(|dir| dir.join("hooks"))
+}
+
+pub fn templates_dir() -> Option<PathBuf> {
+    global_dir().map(|dir| dir.join("templates"))
+}
+
+// Settings that run a command. Like hooks, they are only read from the
+// user's own config file, so a cloned project cannot run anything.
+const GLOBAL_ONLY: &[&str] = &["reminders.command"];
+
+fn check_allowed_in(key: &str, path: &Path) -> Result<(), String> {
+    if GLOBAL_ONLY.contains(&key) && global_path().as_deref() != Some(path) {
+        return Err(tf(
+            "setting_global_only",
+            &[("key", &key), ("file", &path.display())],
+        ));
+    }
+    Ok(())
+}
+
+pub fn local_path() -> PathBuf {
+    PathBuf::from(LOCAL_FILE)
+}
+
+fn not_a_table(name: &str, path: &Path) -> String {
+    tf("not_a_table", &[("name", &name), ("file", &path.display())])
+}
+
+fn read_document(path: &Path) -> Result<Option<DocumentMut>, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => text.parse::<DocumentMut>().map(Some).map_err(|e| {
+            tf(
+                "invalid_config_file",
+                &[("file", &path.display()), ("error", &e)],
+            )
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(read_error(path, e)),
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Config, String> {
+        let mut files: Vec<PathBuf> = global_path().into_iter().collect();
+        files.push(local_path());
+        Config::from_files(&files)
+    }
+
+    // Missing files are skipped; unknown keys and invalid values are errors
+    // that name the file they came from.
+    pub fn from_files(files: &[PathBuf]) -> Result<Config, String> {
+        let mut config = Config::default();
+        for path in files {
+            let Some(document) = read_document(path)? else {
+                continue;
+            };
+            for (table, item) in document.iter() {
+                match table {
+                    "aliases" => {
+                        config.read_aliases(item, path)?;
+                        continue;
+                    }
+                    "reports" => {
+                        config.read_reports(item, path)?;
+                        continue;
+                    }
+                    _ => {}
+                }
+                let Some(entries) = item.as_table_like() else {
+                    return Err(tf(
+                        "unknown_setting_in",
+                        &[("key", &table), ("file", &path.display())],
+                    ));
+                };
+                for (name, item) in entries.iter() {
+                    if table == "urgency" && name == "tag" {
+                        config.read_tag_weights(item, path)?;
+                        continue;
+                    }
+                    let key = format!("{table}.{name}");
+                    let index = SETTINGS.iter().position(|s| s.key == key).ok_or_else(|| {
+                        tf(
+                            "unknown_setting_in",
+                            &[("key", &key), ("file", &path.display())],
+                        )
+                    })?;
+                    let raw = match item.as_value() {
+                        Some(v) if v.is_str() => v.as_str().unwrap_or_default().to_string(),
+                        Some(v) if v.is_integer() || v.is_float() || v.is_bool() => {
+                            v.to_string().trim().to_string()
+                        }
+                        _ => {
+                            return Err(tf(
+                                "setting_not_scalar",
+                                &[("key", &key), ("file", &path.display())],
+                            ))
+                        }
+                    };
+                    check_allowed_in(&key, path)?;
+                    (SETTINGS[index].check)(&raw).map_err(|e| {
+                        tf(
+                            "in_setting",
+                            &[("key", &key), ("file", &path.display()), ("error", &e)],
+                        )
+                    })?;
+                    config.values[index] = (raw, Source::File(path.clone()));
+                }
+            }
+        }
+
+        // Checked once every file is read, since an alias may name a report
+        // defined in another file.
+        for (name, expansion) in &config.aliases {
+            let words = split_line(expansion).unwrap_or_default();
+            let target = Args::parse(&words).command.unwrap_or_default();
+            if !COMMANDS.contains(&target.as_str()) && config.report(&target).is_none() {
+                return Err(tf(
+                    "alias_unknown_command",
+                    &[("name", &name), ("command", &target)],
+                ));
+            }
+            if config.report(name).is_some() {
+                return Err(tf("alias_and_report", &[("name", &name)]));
+            }
+        }
+        Ok(config)
+    }
+
+    fn read_aliases(&mut self, item: &Item, path: &Path) -> Result<(), String> {
+        let table = item
+            .as_table_like()
+            .ok_or_else(|| not_a_table("aliases", path))?;
+        for (name, item) in table.iter() {
+            let context = |e: String| {
+                tf(
+                    "in_alias",
+                    &[("name", &name), ("file", &path.display()), ("error", &e)],
+                )
+            };
+            let expansion = item
+                .as_str()
+                .ok_or_else(|| context(t("alias_not_string").to_string()))?;
+            check_command_name(name).map_err(context)?;
+            if split_line(expansion).map_err(context)?.is_empty() {
+                return Err(context(t("alias_empty").to_string()));
+            }
+            self.aliases.retain(|(n, _)| n != name);
+            self.aliases.push((name.to_string(), expansion.to_string()));
+        }
+        Ok(())
+    }
+
+    // `[urgency.tag]` gives extra weight to tasks with the named tags.
+    fn read_tag_weights(&mut self, item: &Item, path: &Path) -> Result<(), String> {
+        let table = item
+            .as_table_like()
+            .ok_or_else(|| not_a_table("urgency.tag", path))?;
+        for (tag, item) in table.iter() {
+            let weight = item
+                .as_float()
+                .or_else(|| item.as_integer().map(|n| n as f64))
+                .filter(|w| w.is_finite())
+                .ok_or_else(|| {
+                    tf(
+                        "tag_weight_not_number",
+                        &[("tag", &tag), ("file", &path.display())],
+                    )
+                })?;
+            let tag = tag.to_lowercase();
+            self.tag_weights.retain(|(t, _)| *t != tag);
+            self.tag_weights.push((tag, weight));
+        }
+        Ok(())
+    }
+
+    // A report defined again in a later file keeps the fields it does not
+    // override.
+    fn read_reports(&mut self, item: &Item, path: &Path) -> Result<(), String> {
+        let table = item
+            .as_table_like()
+            .ok_or_else(|| not_a_table("reports", path))?;
+        for (name, item) in table.iter() {
+            let context = |e: String| {
+                tf(
+                    "in_report",
+                    &[("name", &name), ("file", &path.display()), ("error", &e)],
+                )
+            };
+            check_command_name(name).map_err(context)?;
+            let fields = item
+                .as_table_like()
+                .ok_or_else(|| context(t("report_not_table").to_string()))?;
+            let mut report = self
+                .report(name)
+                .cloned()
+                .unwrap_or_else(|| Report::new(name));
+            for (field, item) in fields.iter() {
+                let value = report_field(item)
+                    .ok_or_else(|| context(tf("field_not_string", &[("field", &field)])))?;
+                report.set(field, &value).map_err(context)?;
+            }
+            self.reports.retain(|r| r.name != name);
+            self.reports.push(report);
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Result<(&str, &Source), String> {
+        setting(key)?;
+        let index = SETTINGS.iter().position(|s| s.key == key).unwrap();
+        let (value, source) = &self.values[index];
+        Ok((value, source))
+    }
+
+    pub fn entries(&self) -> Vec<Entry> {
+        SETTINGS
+            .iter()
+            .zip(&self.values)
+            .map(|(setting, (value, source))| Entry {
+                key: setting.key,
+                value: value.clone(),
+                source: source.to_string(),
+            })
+            .collect()
+    }
+
+    pub fn aliases(&self) -> &[(String, String)] {
+        &self.aliases
+    }
+
+    pub fn reports(&self) -> &[Report] {
+        &self.reports
+    }
+
+    pub fn report(&self, name: &str) -> Option<&Report> {
+        self.reports.iter().find(|r| r.name == name)
+    }
+
+    // Alias and report names, for completion in the shell.
+    pub fn command_names(&self) -> Vec<String> {
+        self.aliases
+            .iter()
+            .map(|(name, _)| name.clone())
+            .chain(self.reports.iter().map(|r| r.name.clone()))
+            .collect()
+    }
+
+    pub fn expand_alias(&self, args: Args) -> Args {
+        let expansion = args
+            .command
+            .as_deref()
+            .and_then(|command| self.aliases.iter().find(|(name, _)| name == command));
+        match expansion {
+            Some((_, expansion)) => args.expand(&split_line(expansion).unwrap_or_default()),
+            None => args,
+        }
+    }
+
+    // Values were validated when loaded, so falling back to the default
+    // below only guards against programming errors.
+    fn value(&self, key: &str) -> &str {
+        self.get(key).map(|(v, _)| v).unwrap_or_default()
+    }
+
+    pub fn default_priority(&self) -> Priority {
+        Priority::from_name(self.value("tasks.default_priority")).unwrap_or_default()
+    }
+
+    pub fn id_strategy(&self) -> IdStrategy {
+        self.value("tasks.id_strategy").parse().unwrap_or_default()
+    }
+
+    pub fn input_format(&self) -> DatePattern {
+        self.value("dates.input_format").parse().unwrap_or_default()
+    }
+
+    pub fn output_format(&self) -> DatePattern {
+        self.value("dates.output_format")
+            .parse()
+            .unwrap_or_default()
+    }
+
+    pub fn database(&self) -> &str {
+        self.value("storage.database")
+    }
+
+    pub fn color(&self) -> ColorChoice {
+        self.value("display.color").parse().unwrap_or_default()
+    }
+
+    pub fn theme(&self) -> Theme {
+        Theme::parse(self.value("display.theme")).unwrap_or_default()
+    }
+
+    // `auto` follows the locale environment variables.
+    pub fn language(&self) -> Language {
+        match self.value("display.language") {
+            "auto" => Language::from_env(),
+            other => other.parse().unwrap_or_default(),
+        }
+    }
+
+    pub fn separator_width(&self) -> usize {
+        self.value("display.separator_width").parse().unwrap_or(40)
+    }
+
+    pub fn view_filter(&self) -> Option<&str> {
+        Some(self.value("view.filter")).filter(|f| !f.trim().is_empty())
+    }
+
+    pub fn view_sort(&self) -> Option<SortKey> {
+        self.value("view.sort").parse().ok()
+    }
+
+    pub fn urgency(&self) -> Weights {
+        let weight = |key| self.value(key).parse().unwrap_or_default();
+        Weights {
+            priority: weight("urgency.priority"),
+            due: weight("urgency.due"),
+            age: weight("urgency.age"),
+            tags: weight("urgency.tags"),
+            tag: self.tag_weights.clone(),
+        }
+    }
+
+    pub fn reminder_command(&self) -> &str {
+        self.value("reminders.command")
+    }
+
+    // The time of day a task is due, which reminder offsets count from.
+    pub fn due_time(&self) -> NaiveTime {
+        NaiveTime::parse_from_str(self.value("reminders.due_time"), "%H:%M")
+            .unwrap_or(NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default())
+    }
+
+    pub fn reminder_interval(&self) -> Duration {
+        Duration::from_secs(self.value("reminders.interval").parse().unwrap_or(60))
+    }
+
+    pub fn hooks(&self) -> Hooks {
+        let timeout = self.value("hooks.timeout").parse().unwrap_or(10);
+        Hooks::new(hooks_dir(), Duration::from_secs(timeout))
+    }
+}
+
+// Validates and writes one setting, keeping the rest of the file, including
+// comments, as it was.
+pub fn set_value(path: &Path, key: &str, raw: &str) -> Result<(), String> {
+    let setting = setting(key)?;
+    check_allowed_in(key, path)?;
+    (setting.check)(raw)?;
+
+    let mut document = read_document(path)?.unwrap_or_default();
+    let (table, name) = key.split_once('.').unwrap();
+    let item = match setting.kind {
+        Kind::Integer => value(raw.parse::<i64>().map_err(|e| e.to_string())?),
+        Kind::Number => match raw.parse::<i64>() {
+            Ok(whole) => value(whole),
+            Err(_) => value(raw.parse::<f64>().map_err(|e| e.to_string())?),
+        },
+        Kind::Text => value(raw),
+    };
+    if !document.contains_table(table) {
+        document[table] = Item::Table(Default::default());
+    }
+    document[table][name] = item;
+
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|e| create_error(dir, e))?;
+    }
+    fs::write(path, document.to_string()).map_err(|e| write_error(path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "todo_cli_config_{}_{name}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn defaults_without_files() {
+        let config = Config::from_files(&[PathBuf::from("/nonexistent/config.toml")]).unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.database(), "task_database.xml");
+        assert_eq!(config.separator_width(), 40);
+        assert_eq!(config.view_filter(), None);
+        assert_eq!(config.view_sort(), None);
+        assert_eq!(config.get("display.color"), Ok(("auto", &Source::Default)));
+    }
+
+    #[test]
+    fn later_files_override_earlier_ones() {
+        let global = temp_file(
+            "global",
+            "[tasks]\ndefault_priority = \"minor\"\n[display]\nseparator_width = 20\n",
+        );
+        let local = temp_file("local", "[tasks]\ndefault_priority = \"asap\"\n");
+        let config = Config::from_files(&[global.clone(), local.clone()]).unwrap();
+        assert_eq!(config.default_priority(), Priority::Asap);
+        assert_eq!(config.separator_width(), 20);
+        assert_eq!(
+            config.get("tasks.default_priority").unwrap().1,
+            &Source::File(local.clone())
+        );
+        assert_eq!(
+            config.get("display.separator_width").unwrap().1,
+            &Source::File(global.clone())
+        );
+        fs::remove_file(global).unwrap();
+        fs::remove_file(local).unwrap();
+    }
+
+    #[test]
+    fn invalid_files_name_the_problem() {
+        let path = temp_file("invalid", "[tasks]\ndefault_priority = \"urgent\"\n");
+        let error = Config::from_files(std::slice::from_ref(&path)).unwrap_err();
+        assert!(error.contains("tasks.default_priority"));
+        assert!(error.contains(&path.display().to_string()));
+
+        fs::write(&path, "[tasks]\ncolour = \"red\"\n").unwrap();
+        assert!(Config::from_files(std::slice::from_ref(&path))
+            .unwrap_err()
+            .contains("Unknown setting 'tasks.colour'"));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reads_aliases_and_reports() {
+        let global = temp_file(
+            "reports_global",
+            "[aliases]\ntoday = \"view due:today --sort priority\"\nwk = \"weekly --format compact\"\n\n[reports.weekly]\nfilter = \"due:this_week\"\ncolumns = [\"id\", \"description\"]\n",
+        );
+        let local = temp_file("reports_local", "[reports.weekly]\ngroup = \"priority\"\n");
+        let config = Config::from_files(&[global.clone(), local.clone()]).unwrap();
+
+        let report = config.report("weekly").unwrap();
+        assert_eq!(report.filter, "due:this_week");
+        assert_eq!(report.columns.len(), 2);
+        assert!(report.group.is_some());
+        assert_eq!(config.command_names(), vec!["today", "wk", "weekly"]);
+
+        let args = config.expand_alias(Args::parse(&["today".to_string()]));
+        assert_eq!(args.command.as_deref(), Some("view"));
+        assert_eq!(args.value("sort"), Some("priority"));
+
+        fs::write(&local, "[aliases]\nview = \"agenda\"\n").unwrap();
+        assert!(Config::from_files(std::slice::from_ref(&local))
+            .unwrap_err()
+            .contains("built-in command"));
+        fs::write(&local, "[aliases]\nloop = \"loop\"\n").unwrap();
+        assert!(Config::from_files(std::slice::from_ref(&local))
+            .unwrap_err()
+            .contains("unknown command 'loop'"));
+        fs::write(&local, "[reports.bad]\nsort = \"size\"\n").unwrap();
+        assert!(Config::from_files(std::slice::from_ref(&local))
+            .unwrap_err()
+            .contains("report 'bad'"));
+        fs::remove_file(global).unwrap();
+        fs::remove_file(local).unwrap();
+    }
+
+    #[test]
+    fn reads_urgency_weights() {
+        let path = temp_file(
+            "urgency",
+            "[urgency]\ndue = 8.5\nage = 0\n\n[urgency.tag]\nNext = 15\nsomeday = -4.5\n",
+        );
+        let weights = Config::from_files(std::slice::from_ref(&path))
+            .unwrap()
+            .urgency();
+        assert_eq!(weights.due, 8.5);
+        assert_eq!(weights.age, 0.0);
+        assert_eq!(weights.priority, 6.0);
+        assert_eq!(
+            weights.tag,
+            vec![("next".to_string(), 15.0), ("someday".to_string(), -4.5)]
+        );
+
+        set_value(&path, "urgency.priority", "2.5").unwrap();
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("priority = 2.5"));
+        assert!(set_value(&path, "urgency.priority", "high").is_err());
+        fs::write(&path, "[urgency.tag]\nnext = \"high\"\n").unwrap();
+        assert!(Config::from_files(std::slice::from_ref(&path)).is_err());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn set_value_keeps_comments_and_validates() {
+        let path = temp_file("set", "# my settings\n[view]\nsort = \"due\" # keep\n");
+        set_value(&path, "display.separator_width", "30").unwrap();
+        set_value(&path, "view.filter", "-tag:someday").unwrap();
+        assert!(set_value(&path, "display.separator_width", "wide").is_err());
+        assert!(set_value(&path, "view.colour", "red").is_err());
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains("# my settings"));
+        assert!(text.contains("sort = \"due\" # keep"));
+        let config = Config::from_files(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(config.separator_width(), 30);
+        assert_eq!(config.view_filter(), Some("-tag:someday"));
+        assert_eq!(config.view_sort(), Some(SortKey::Due));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn only_the_global_file_sets_the_reminder_command() {
+        let local = temp_file("reminders_local", "[reminders]\ncommand = \"sh evil.sh\"\n");
+        let error = Config::from_files(std::slice::from_ref(&local)).unwrap_err();
+        assert!(error.contains("reminders.command"), "{error}");
+        assert!(set_value(&local, "reminders.command", "sh evil.sh").is_err());
+
+        fs::write(&local, "[reminders]\ndue_time = \"08:00\"\n").unwrap();
+        let config = Config::from_files(std::slice::from_ref(&local)).unwrap();
+        assert_eq!(config.reminder_command(), "auto");
+        fs::remove_file(local).unwrap();
+    }
+}
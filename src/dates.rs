@@ -0,0 +1,409 @@
+use crate::i18n::tf;
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime};
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+const STORED_DATE_FORMAT: &str = "%Y-%m-%d";
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub fn now() -> NaiveDateTime {
+    Local::now().naive_local()
+}
+
+pub fn format_timestamp(at: NaiveDateTime) -> String {
+    at.format(TIMESTAMP_FORMAT).to_string()
+}
+
+pub fn parse_timestamp(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s.trim(), TIMESTAMP_FORMAT).ok()
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum DatePart {
+    Month,
+    Day,
+    Year,
+}
+
+// A date layout such as `MM/DD/YYYY` or `DD.MM.YYYY`, used for typing and
+// displaying due dates. Due dates are always stored as ISO `YYYY-MM-DD`, so
+// files mean the same thing whatever layout their readers use.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DatePattern {
+    order: [DatePart; 3],
+    separator: char,
+}
+
+impl Default for DatePattern {
+    fn default() -> Self {
+        DatePattern {
+            order: [DatePart::Month, DatePart::Day, DatePart::Year],
+            separator: '/',
+        }
+    }
+}
+
+impl std::str::FromStr for DatePattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        // Shorthands for the common locale orders.
+        let s = match s.to_uppercase().as_str() {
+            "MDY" => "MM/DD/YYYY",
+            "DMY" => "DD/MM/YYYY",
+            "YMD" | "ISO" => "YYYY-MM-DD",
+            _ => s,
+        };
+        let invalid = || tf("invalid_date_pattern", &[("format", &s)]);
+        let separator = s
+            .chars()
+            .find(|c| matches!(c, '/' | '-' | '.'))
+            .ok_or_else(invalid)?;
+        let parts: Vec<DatePart> = s
+            .split(separator)
+            .map(|part| match part.to_uppercase().as_str() {
+                "MM" => Ok(DatePart::Month),
+                "DD" => Ok(DatePart::Day),
+                "YYYY" => Ok(DatePart::Year),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<_, _>>()?;
+        let order: [DatePart; 3] = parts.try_into().map_err(|_| invalid())?;
+        if order[0] == order[1] || order[1] == order[2] || order[0] == order[2] {
+            return Err(invalid());
+        }
+        Ok(DatePattern { order, separator })
+    }
+}
+
+impl std::fmt::Display for DatePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let names: Vec<&str> = self
+            .order
+            .iter()
+            .map(|part| match part {
+                DatePart::Month => "MM",
+                DatePart::Day => "DD",
+                DatePart::Year => "YYYY",
+            })
+            .collect();
+        write!(f, "{}", names.join(&self.separator.to_string()))
+    }
+}
+
+impl DatePattern {
+    pub fn format(&self, date: NaiveDate) -> String {
+        let parts: Vec<String> = self
+            .order
+            .iter()
+            .map(|part| match part {
+                DatePart::Month => format!("{:02}", date.month()),
+                DatePart::Day => format!("{:02}", date.day()),
+                DatePart::Year => format!("{:04}", date.year()),
+            })
+            .collect();
+        parts.join(&self.separator.to_string())
+    }
+
+    // The day and month in this layout, for labels that leave out the year.
+    pub fn format_day_month(&self, date: NaiveDate) -> String {
+        let parts: Vec<String> = self
+            .order
+            .iter()
+            .filter_map(|part| match part {
+                DatePart::Month => Some(format!("{:02}", date.month())),
+                DatePart::Day => Some(format!("{:02}", date.day())),
+                DatePart::Year => None,
+            })
+            .collect();
+        parts.join(&self.separator.to_string())
+    }
+
+    // Shows a stored due date in this layout. Dates that cannot be parsed
+    // are shown as they are.
+    pub fn display(&self, stored: &str) -> String {
+        match parse_stored_date(stored) {
+            Some(date) => self.format(date),
+            None => stored.to_string(),
+        }
+    }
+
+    // Accepts any of `/`, `-`, `.` or a space as separator, unpadded month and
+    // day, two-digit years, and all eight digits without separators. Returns
+    // the date in the stored `YYYY-MM-DD` form. ISO dates are unambiguous and
+    // accepted whatever the layout.
+    pub fn parse_input(&self, input: &str) -> Result<String, ()> {
+        let trimmed = input.trim();
+        if trimmed
+            .split('-')
+            .next()
+            .is_some_and(|year| year.len() == 4)
+        {
+            if let Ok(date) = NaiveDate::parse_from_str(trimmed, STORED_DATE_FORMAT) {
+                return Ok(format_stored_date(date));
+            }
+        }
+        let parts: Vec<String> = if input.len() == 8 && input.chars().all(|c| c.is_ascii_digit()) {
+            let mut rest = input;
+            self.order
+                .iter()
+                .map(|part| {
+                    let width = if *part == DatePart::Year { 4 } else { 2 };
+                    let (head, tail) = rest.split_at(width);
+                    rest = tail;
+                    head.to_string()
+                })
+                .collect()
+        } else {
+            input
+                .replace(['-', '.', ' '], "/")
+                .split('/')
+                .map(str::to_string)
+                .collect()
+        };
+        if parts.len() != 3 {
+            return Err(());
+        }
+
+        let part = |wanted: DatePart| {
+            let index = self.order.iter().position(|p| *p == wanted).unwrap();
+            parts[index].as_str()
+        };
+
+        // Zero-pad single-digit month/day; expand 2-digit year to 4-digit
+        fn pad_two(s: &str) -> Result<String, ()> {
+            match s.len() {
+                1 => Ok(format!("0{}", s)),
+                2 => Ok(s.to_string()),
+                _ => Err(()),
+            }
+        }
+        let month_str = pad_two(part(DatePart::Month))?;
+        let day_str = pad_two(part(DatePart::Day))?;
+        let year = part(DatePart::Year);
+        let year_str = match year.len() {
+            2 => format!("20{}", year),
+            4 => year.to_string(),
+            _ => return Err(()),
+        };
+
+        let month: u32 = month_str.parse().map_err(|_| ())?;
+        let day: u32 = day_str.parse().map_err(|_| ())?;
+        let year: i32 = year_str.parse().map_err(|_| ())?;
+
+        NaiveDate::from_ymd_opt(year, month, day)
+            .map(format_stored_date)
+            .ok_or(())
+    }
+}
+
+// Parses a span such as `30d`, `2w` or `12h`. The amount is a plain number,
+// without a sign.
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    let unit = input.chars().last()?;
+    let digits = &input[..input.len() - unit.len_utf8()];
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: i64 = digits.parse().ok()?;
+    match unit.to_ascii_lowercase() {
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+// The time an age such as `30d` reaches back to from `now`. Ages must be
+// longer than zero.
+pub fn age_cutoff(input: &str, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let age = parse_duration(input).filter(|age| *age > Duration::zero())?;
+    now.checked_sub_signed(age)
+}
+
+pub fn format_stored_date(date: NaiveDate) -> String {
+    date.format(STORED_DATE_FORMAT).to_string()
+}
+
+// Due dates are stored as ISO `YYYY-MM-DD`. Files written before that used
+// `MM/DD/YYYY`, possibly unpadded, which is still read until they are
+// migrated.
+pub fn parse_stored_date(s: &str) -> Option<NaiveDate> {
+    let s = s.trim();
+    NaiveDate::parse_from_str(s, STORED_DATE_FORMAT)
+        .or_else(|_| NaiveDate::parse_from_str(s, "%m/%d/%Y"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_due_date(input: &str) -> Result<String, ()> {
+        DatePattern::default().parse_input(input)
+    }
+
+    #[test]
+    fn day_month_follows_the_layout() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        assert_eq!(DatePattern::default().format_day_month(date), "01/05");
+        let dmy: DatePattern = "DD.MM.YYYY".parse().unwrap();
+        assert_eq!(dmy.format_day_month(date), "05.01");
+        let iso: DatePattern = "ISO".parse().unwrap();
+        assert_eq!(iso.format_day_month(date), "01-05");
+    }
+
+    #[test]
+    fn parse_padded_and_unpadded() {
+        let expected = NaiveDate::from_ymd_opt(2023, 1, 5);
+        assert_eq!(parse_stored_date("01/05/2023"), expected);
+        assert_eq!(parse_stored_date("1/5/2023"), expected);
+        assert_eq!(parse_stored_date(" 1/5/2023 "), expected);
+    }
+
+    #[test]
+    fn parse_iso() {
+        assert_eq!(
+            parse_stored_date("2023-06-10"),
+            NaiveDate::from_ymd_opt(2023, 6, 10)
+        );
+    }
+
+    #[test]
+    fn timestamp_round_trip() {
+        let at = parse_timestamp("2026-10-19T09:30:05").unwrap();
+        assert_eq!(format_timestamp(at), "2026-10-19T09:30:05");
+        assert_eq!(parse_timestamp("2026-10-19"), None);
+    }
+
+    #[test]
+    fn parse_durations() {
+        assert_eq!(parse_duration("30d"), Some(Duration::days(30)));
+        assert_eq!(parse_duration("2W"), Some(Duration::weeks(2)));
+        assert_eq!(parse_duration("12h"), Some(Duration::hours(12)));
+        assert_eq!(parse_duration("d"), None);
+        assert_eq!(parse_duration("30"), None);
+        assert_eq!(parse_duration("3y"), None);
+        assert_eq!(parse_duration("-5d"), None);
+        assert_eq!(parse_duration("+5d"), None);
+        assert_eq!(parse_duration("999999999999999w"), None);
+    }
+
+    #[test]
+    fn age_cutoffs_reach_into_the_past() {
+        let now = parse_timestamp("2026-10-19T09:30:00").unwrap();
+        assert_eq!(
+            age_cutoff("2d", now),
+            parse_timestamp("2026-10-17T09:30:00")
+        );
+        assert_eq!(age_cutoff("0d", now), None);
+        assert_eq!(age_cutoff("-5d", now), None);
+        assert_eq!(age_cutoff("999999999999999w", now), None);
+        assert_eq!(age_cutoff("1000000000d", now), None);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(parse_stored_date(""), None);
+        assert_eq!(parse_stored_date("02/30/2023"), None);
+        assert_eq!(parse_stored_date("someday"), None);
+    }
+
+    #[test]
+    fn test_parse_due_date_valid() {
+        assert_eq!(parse_due_date("01/06/2026"), Ok("2026-01-06".to_string()));
+    }
+
+    #[test]
+    fn test_parse_due_date_dash_delimited() {
+        assert_eq!(parse_due_date("01-06-2026"), Ok("2026-01-06".to_string()));
+    }
+
+    #[test]
+    fn test_parse_due_date_eight_digit() {
+        assert_eq!(parse_due_date("01062026"), Ok("2026-01-06".to_string()));
+    }
+
+    #[test]
+    fn test_parse_due_date_invalid_month() {
+        assert_eq!(parse_due_date("13/06/2026"), Err(()));
+    }
+
+    #[test]
+    fn test_parse_due_date_invalid_day() {
+        assert_eq!(parse_due_date("01/32/2026"), Err(()));
+    }
+
+    #[test]
+    fn test_parse_due_date_short_digits() {
+        assert_eq!(parse_due_date("0106202"), Err(()));
+    }
+
+    #[test]
+    fn test_parse_due_date_single_digit_parts() {
+        assert_eq!(parse_due_date("1/1/2026"), Ok("2026-01-01".to_string()));
+    }
+
+    #[test]
+    fn test_parse_due_date_two_digit_year() {
+        assert_eq!(parse_due_date("01/06/26"), Ok("2026-01-06".to_string()));
+    }
+
+    #[test]
+    fn test_parse_due_date_single_digit_and_two_digit_year() {
+        assert_eq!(parse_due_date("1/1/26"), Ok("2026-01-01".to_string()));
+    }
+
+    #[test]
+    fn parse_due_date_rejects_impossible_days() {
+        assert_eq!(parse_due_date("02/30/2026"), Err(()));
+        assert_eq!(parse_due_date("02/29/2028"), Ok("2028-02-29".to_string()));
+    }
+
+    #[test]
+    fn locale_shorthands() {
+        assert_eq!(
+            "dmy".parse::<DatePattern>().unwrap().to_string(),
+            "DD/MM/YYYY"
+        );
+        assert_eq!(
+            "MDY".parse::<DatePattern>().unwrap(),
+            DatePattern::default()
+        );
+        assert_eq!(
+            "iso".parse::<DatePattern>().unwrap().to_string(),
+            "YYYY-MM-DD"
+        );
+        assert_eq!(
+            "YMD".parse::<DatePattern>().unwrap().to_string(),
+            "YYYY-MM-DD"
+        );
+    }
+
+    #[test]
+    fn date_patterns_parse_and_display() {
+        let pattern: DatePattern = "dd.mm.yyyy".parse().unwrap();
+        assert_eq!(pattern.to_string(), "DD.MM.YYYY");
+        assert_eq!(pattern.display("2026-01-15"), "15.01.2026");
+        assert_eq!(pattern.display("01/15/2026"), "15.01.2026");
+        assert_eq!(pattern.display("someday"), "someday");
+        assert!("MM/MM/YYYY".parse::<DatePattern>().is_err());
+        assert!("MM/DD".parse::<DatePattern>().is_err());
+        assert!("YYYY MM DD".parse::<DatePattern>().is_err());
+    }
+
+    #[test]
+    fn date_patterns_parse_input_in_their_order() {
+        let dmy: DatePattern = "DD/MM/YYYY".parse().unwrap();
+        assert_eq!(dmy.parse_input("15/1/26"), Ok("2026-01-15".to_string()));
+        assert_eq!(dmy.parse_input("15012026"), Ok("2026-01-15".to_string()));
+        assert_eq!(dmy.parse_input("01/15/2026"), Err(()));
+        assert_eq!(dmy.parse_input("2026-01-15"), Ok("2026-01-15".to_string()));
+        let ymd: DatePattern = "YYYY-MM-DD".parse().unwrap();
+        assert_eq!(ymd.parse_input("2026-01-15"), Ok("2026-01-15".to_string()));
+        assert_eq!(ymd.parse_input("20260115"), Ok("2026-01-15".to_string()));
+    }
+}
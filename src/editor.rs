@@ -0,0 +1,263 @@
+use crate::bulk::FieldUpdate;
+use crate::dates::DatePattern;
+use crate::i18n::{read_error, t, tf, write_error};
+use crate::models::Task;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const SEPARATOR: &str = "---";
+const ERROR_PREFIX: &str = "# error: ";
+
+// Renders a task as a small front-matter document: one `field: value` line per
+// field, a `---` separator, then the notes as free text. The due date is
+// written in the `dates` layout.
+pub fn render_document(task: &Task, dates: &DatePattern) -> String {
+    format!(
+        "{}\ndescription: {}\ndue: {}\npriority: {}\ntags: {}\n{SEPARATOR}\n{}\n",
+        t("editor_help"),
+        task.description,
+        dates.display(&task.due_date),
+        task.priority,
+        task.tags.join(", "),
+        task.notes
+    )
+}
+
+// Parses an edited document back into field updates. Fields left out of the
+// document are left unchanged. Returns `Ok(None)` when the document was
+// emptied, which cancels the edit.
+pub fn parse_document(
+    text: &str,
+    dates: &DatePattern,
+) -> Result<Option<Vec<FieldUpdate>>, Vec<String>> {
+    let mut lines = text.lines();
+    let mut updates: Vec<FieldUpdate> = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen: Vec<String> = Vec::new();
+    let mut found_separator = false;
+
+    for line in lines.by_ref() {
+        let line = line.trim();
+        if line == SEPARATOR {
+            found_separator = true;
+            break;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((field, value)) = line.split_once(':') else {
+            errors.push(tf("editor_expected_field", &[("line", &line)]));
+            continue;
+        };
+        let field = field.trim().to_lowercase();
+        let value = value.trim();
+        if seen.contains(&field) {
+            errors.push(tf("editor_duplicate_field", &[("field", &field)]));
+            continue;
+        }
+        seen.push(field.clone());
+
+        let assignment = match field.as_str() {
+            "priority" if value.is_empty() => "priority=none".to_string(),
+            "notes" => {
+                errors.push(tf("editor_notes_field", &[("separator", &SEPARATOR)]));
+                continue;
+            }
+            "description" | "due" | "priority" | "tags" => format!("{field}={value}"),
+            _ => {
+                errors.push(tf("editor_unknown_field", &[("field", &field)]));
+                continue;
+            }
+        };
+        match FieldUpdate::parse(&assignment, dates) {
+            Ok(update) => updates.push(update),
+            Err(e) => errors.push(format!("{field}: {e}")),
+        }
+    }
+
+    let notes = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+    if !found_separator {
+        if updates.is_empty() && errors.is_empty() {
+            return Ok(None);
+        }
+        errors.push(tf("editor_missing_separator", &[("separator", &SEPARATOR)]));
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    updates.push(FieldUpdate::Notes(notes));
+    Ok(Some(updates))
+}
+
+// Puts the errors at the top of the document so they are visible when the
+// editor is reopened. Errors from a previous attempt are replaced.
+pub fn annotate(text: &str, errors: &[String]) -> String {
+    let mut annotated: Vec<String> = errors
+        .iter()
+        .map(|e| format!("{ERROR_PREFIX}{e}"))
+        .collect();
+    annotated.extend(
+        text.lines()
+            .filter(|line| !line.starts_with(ERROR_PREFIX))
+            .map(str::to_string),
+    );
+    annotated.join("\n") + "\n"
+}
+
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+fn launch(path: &Path) -> Result<(), String> {
+    let command = editor_command();
+    let mut parts = command.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .map_err(|e| {
+            tf(
+                "editor_not_started",
+                &[("command", &command), ("error", &e)],
+            )
+        })?;
+    if !status.success() {
+        return Err(tf(
+            "editor_failed",
+            &[("command", &command), ("status", &status)],
+        ));
+    }
+    Ok(())
+}
+
+// Opens the task in the user's editor until the document parses, and returns
+// the updates to apply. Returns `Ok(None)` if the user cancelled, either by
+// emptying the document or by leaving it unchanged after an error.
+pub fn edit_task(task: &Task, dates: &DatePattern) -> Result<Option<Vec<FieldUpdate>>, String> {
+    let path = std::env::temp_dir().join(format!(
+        "todo_cli-{}-{}.md",
+        std::process::id(),
+        if task.id.is_empty() { "new" } else { &task.id }
+    ));
+    let mut contents = render_document(task, dates);
+    let mut after_error = false;
+
+    let result = loop {
+        if let Err(e) = fs::write(&path, &contents) {
+            break Err(write_error(&path, e));
+        }
+        if let Err(e) = launch(&path) {
+            break Err(e);
+        }
+        let edited = match fs::read_to_string(&path) {
+            Ok(edited) => edited,
+            Err(e) => break Err(read_error(&path, e)),
+        };
+        if after_error && edited == contents {
+            break Ok(None);
+        }
+        match parse_document(&edited, dates) {
+            Ok(updates) => break Ok(updates),
+            Err(errors) => {
+                contents = annotate(&edited, &errors);
+                after_error = true;
+            }
+        }
+    };
+
+    let _ = fs::remove_file(&path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+
+    fn task() -> Task {
+        Task {
+            id: "3D".to_string(),
+            description: "Buy groceries".to_string(),
+            due_date: "2026-01-15".to_string(),
+            priority: Priority::Important,
+            notes: "Don't forget milk\nand eggs".to_string(),
+            tags: vec!["errands".to_string(), "home".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn apply(text: &str) -> Task {
+        let mut edited = task();
+        for update in parse_document(text, &DatePattern::default())
+            .unwrap()
+            .unwrap()
+        {
+            update.apply(&mut edited);
+        }
+        edited
+    }
+
+    #[test]
+    fn unchanged_document_round_trips() {
+        assert_eq!(
+            apply(&render_document(&task(), &DatePattern::default())),
+            task()
+        );
+    }
+
+    #[test]
+    fn empty_fields_clear_values() {
+        let edited = apply("description: Buy groceries\ndue:\npriority:\ntags:\n---\n");
+        assert_eq!(edited.due_date, "");
+        assert_eq!(edited.priority, Priority::None);
+        assert!(edited.tags.is_empty());
+        assert_eq!(edited.notes, "");
+    }
+
+    #[test]
+    fn invalid_fields_are_reported_by_field() {
+        let errors = parse_document(
+            "description:\ndue: 13/40/2026\ncolour: red\nnonsense\n---\n",
+            &DatePattern::default(),
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].starts_with("description:"));
+        assert!(errors[1].starts_with("due:"));
+        assert!(errors[3].contains("'nonsense'"));
+    }
+
+    #[test]
+    fn due_dates_use_the_input_format() {
+        let dates: DatePattern = "DD.MM.YYYY".parse().unwrap();
+        let document = render_document(&task(), &dates);
+        assert!(document.contains("due: 15.01.2026"));
+        let updates = parse_document(&document.replace("15.01", "20.01"), &dates)
+            .unwrap()
+            .unwrap();
+        assert!(updates.contains(&FieldUpdate::DueDate("2026-01-20".to_string())));
+    }
+
+    #[test]
+    fn emptied_document_cancels() {
+        assert_eq!(
+            parse_document("# just a comment\n\n", &DatePattern::default()),
+            Ok(None)
+        );
+        assert!(parse_document("description: Walk dog\n", &DatePattern::default()).is_err());
+    }
+
+    #[test]
+    fn annotate_replaces_previous_errors() {
+        let once = annotate("description:\n---\n", &["first".to_string()]);
+        let twice = annotate(&once, &["second".to_string()]);
+        assert_eq!(twice, "# error: second\ndescription:\n---\n");
+    }
+}
@@ -0,0 +1,363 @@
+use crate::agenda::{bucket_for, Bucket};
+use crate::dates::{self, parse_stored_date, DatePattern};
+use crate::i18n::tf;
+use crate::models::{Priority, Status, Task};
+use crate::urgency;
+use chrono::NaiveDate;
+
+#[derive(Debug, PartialEq, Clone)]
+enum Term {
+    Id(String),
+    Tag(String),
+    Priority(Priority),
+    Due(DueMatch),
+    Status(Status),
+    Postponed(usize),
+    Text(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum DueMatch {
+    Bucket(Bucket),
+    On(NaiveDate),
+}
+
+// A conjunction of terms, e.g. `tag:sprint12 priority:minor -due:none`.
+// Prefixing a term with `-` negates it; bare words search the description
+// and notes. `postponed:N` matches tasks postponed at least N times.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Filter {
+    terms: Vec<(bool, Term)>,
+}
+
+fn parse_bucket(value: &str) -> Option<Bucket> {
+    match value {
+        "overdue" => Some(Bucket::Overdue),
+        "today" => Some(Bucket::Today),
+        "tomorrow" => Some(Bucket::Tomorrow),
+        "this_week" | "week" => Some(Bucket::ThisWeek),
+        "next_week" => Some(Bucket::NextWeek),
+        "later" => Some(Bucket::Later),
+        "none" => Some(Bucket::NoDate),
+        _ => None,
+    }
+}
+
+fn parse_term(word: &str, dates: &DatePattern) -> Result<Term, String> {
+    let Some((key, value)) = word.split_once(':') else {
+        return Ok(Term::Text(word.to_lowercase()));
+    };
+    let lowered = value.to_lowercase();
+    match key.to_lowercase().as_str() {
+        "id" => Ok(Term::Id(value.to_uppercase())),
+        "tag" => Ok(Term::Tag(lowered)),
+        "priority" => Priority::from_name(&lowered)
+            .map(Term::Priority)
+            .ok_or_else(|| tf("filter_unknown_priority", &[("value", &value)])),
+        "due" => parse_bucket(&lowered)
+            .map(DueMatch::Bucket)
+            .or_else(|| {
+                dates
+                    .parse_input(value)
+                    .ok()
+                    .and_then(|date| parse_stored_date(&date))
+                    .map(DueMatch::On)
+            })
+            .map(Term::Due)
+            .ok_or_else(|| tf("filter_unknown_due", &[("value", &value)])),
+        "status" => match lowered.as_str() {
+            "open" => Ok(Term::Status(Status::Open)),
+            "done" => Ok(Term::Status(Status::Done)),
+            _ => Err(tf("filter_unknown_status", &[("value", &value)])),
+        },
+        "postponed" => value
+            .parse()
+            .map(Term::Postponed)
+            .map_err(|_| tf("filter_invalid_postponed", &[("value", &value)])),
+        other => Err(tf("filter_unknown_key", &[("key", &other)])),
+    }
+}
+
+impl Filter {
+    // Due dates in the filter are read in the configured input layout, as
+    // `new` and `edit` read them.
+    pub fn parse(input: &str, dates: &DatePattern) -> Result<Filter, String> {
+        let mut terms = Vec::new();
+        for word in input.split_whitespace() {
+            let (negated, word) = match word.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, word),
+            };
+            terms.push((negated, parse_term(word, dates)?));
+        }
+        Ok(Filter { terms })
+    }
+
+    // Filters in config files are checked before the date layout is known,
+    // since it may be set further down or in another file. A due date there
+    // only has to be valid in one of the common layouts.
+    pub fn check(input: &str) -> Result<(), String> {
+        let mut result = Ok(());
+        for layout in ["MDY", "DMY", "YMD"] {
+            let dates: DatePattern = layout.parse()?;
+            result = Filter::parse(input, &dates).map(|_| ());
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
+    }
+
+    pub fn matches(&self, task: &Task, today: NaiveDate) -> bool {
+        self.terms.iter().all(|(negated, term)| {
+            let hit = match term {
+                Term::Id(id) => &task.id == id,
+                Term::Tag(tag) => task.tags.iter().any(|t| t.to_lowercase() == *tag),
+                Term::Priority(priority) => &task.priority == priority,
+                Term::Due(DueMatch::Bucket(bucket)) => bucket_for(task.due(), today) == *bucket,
+                Term::Due(DueMatch::On(date)) => task.due() == Some(*date),
+                Term::Status(status) => &task.status == status,
+                Term::Postponed(times) => task.postpone_count() >= *times,
+                Term::Text(text) => {
+                    task.description.to_lowercase().contains(text)
+                        || task.notes.to_lowercase().contains(text)
+                }
+            };
+            hit != *negated
+        })
+    }
+
+    pub fn apply<'a>(&self, tasks: &'a [Task], today: NaiveDate) -> Vec<&'a Task> {
+        tasks.iter().filter(|t| self.matches(t, today)).collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SortKey {
+    Due,
+    Priority,
+    Description,
+    Created,
+    Postponed,
+    Urgency,
+    Manual,
+}
+
+impl std::str::FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "due" => Ok(SortKey::Due),
+            "priority" => Ok(SortKey::Priority),
+            "description" => Ok(SortKey::Description),
+            "created" => Ok(SortKey::Created),
+            "postponed" => Ok(SortKey::Postponed),
+            "urgency" => Ok(SortKey::Urgency),
+            "manual" => Ok(SortKey::Manual),
+            other => Err(tf("unknown_sort_key", &[("key", &other)])),
+        }
+    }
+}
+
+impl SortKey {
+    pub fn next(self) -> SortKey {
+        match self {
+            SortKey::Due => SortKey::Priority,
+            SortKey::Priority => SortKey::Description,
+            SortKey::Description => SortKey::Created,
+            SortKey::Created => SortKey::Postponed,
+            SortKey::Postponed => SortKey::Urgency,
+            SortKey::Urgency => SortKey::Manual,
+            SortKey::Manual => SortKey::Due,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Due => "due",
+            SortKey::Priority => "priority",
+            SortKey::Description => "description",
+            SortKey::Created => "created",
+            SortKey::Postponed => "postponed",
+            SortKey::Urgency => "urgency",
+            SortKey::Manual => "manual",
+        }
+    }
+}
+
+// Stable sort; tasks without a due date go last when sorting by due date.
+pub fn sort_tasks(tasks: &mut [&Task], key: SortKey, reverse: bool) {
+    let today = dates::today();
+    tasks.sort_by(|a, b| {
+        let by_due = || match (a.due(), b.due()) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            (x, y) => y.is_some().cmp(&x.is_some()),
+        };
+        let ordering = match key {
+            SortKey::Due => by_due().then_with(|| a.priority.cmp(&b.priority)),
+            SortKey::Priority => a.priority.cmp(&b.priority).then_with(by_due),
+            SortKey::Description => a
+                .description
+                .to_lowercase()
+                .cmp(&b.description.to_lowercase()),
+            SortKey::Created => a.created_at().cmp(&b.created_at()),
+            // Most postponed first.
+            SortKey::Postponed => b
+                .postpone_count()
+                .cmp(&a.postpone_count())
+                .then_with(by_due),
+            // Most urgent first, scored with the configured weights.
+            SortKey::Urgency => urgency::score(b, today)
+                .total_cmp(&urgency::score(a, today))
+                .then_with(by_due),
+            // The order set with `move`; unranked tasks go last.
+            SortKey::Manual => a
+                .rank
+                .is_empty()
+                .cmp(&b.rank.is_empty())
+                .then_with(|| a.rank.cmp(&b.rank)),
+        };
+        if reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+    }
+
+    fn task(id: &str, priority: Priority, due_date: &str, tags: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            description: format!("Task {id}"),
+            due_date: due_date.to_string(),
+            priority,
+            notes: "Call the vendor".to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn ids(filter: &str, tasks: &[Task]) -> Vec<String> {
+        Filter::parse(filter, &DatePattern::default())
+            .unwrap()
+            .apply(tasks, today())
+            .iter()
+            .map(|t| t.id.clone())
+            .collect()
+    }
+
+    fn sample() -> Vec<Task> {
+        vec![
+            task("1A", Priority::Minor, "10/20/2026", &["sprint12"]),
+            task("2B", Priority::Asap, "10/21/2026", &["Sprint12", "ops"]),
+            task("3C", Priority::Minor, "", &[]),
+        ]
+    }
+
+    #[test]
+    fn filter_by_tag_is_case_insensitive() {
+        assert_eq!(ids("tag:sprint12", &sample()), vec!["1A", "2B"]);
+    }
+
+    #[test]
+    fn filter_terms_are_conjunctive() {
+        assert_eq!(ids("tag:sprint12 priority:minor", &sample()), vec!["1A"]);
+    }
+
+    #[test]
+    fn filter_negation() {
+        assert_eq!(ids("-tag:ops", &sample()), vec!["1A", "3C"]);
+        assert_eq!(ids("-due:none", &sample()), vec!["1A", "2B"]);
+    }
+
+    #[test]
+    fn filter_due_buckets_and_dates() {
+        assert_eq!(ids("due:overdue", &sample()), vec!["1A"]);
+        assert_eq!(ids("due:today", &sample()), vec!["2B"]);
+        assert_eq!(ids("due:10-21-2026", &sample()), vec!["2B"]);
+        assert_eq!(ids("due:2026-10-21", &sample()), vec!["2B"]);
+    }
+
+    #[test]
+    fn filter_dates_follow_the_input_format() {
+        let dmy: DatePattern = "DMY".parse().unwrap();
+        let filter = Filter::parse("due:21.10.2026", &dmy).unwrap();
+        let tasks = sample();
+        let ids: Vec<&str> = filter
+            .apply(&tasks, today())
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["2B"]);
+        assert!(Filter::parse("due:10/21/2026", &dmy).is_err());
+        assert!(Filter::check("due:21/10/2026").is_ok());
+        assert!(Filter::check("due:10/21/2026").is_ok());
+        assert!(Filter::check("due:31/31/2026").is_err());
+    }
+
+    #[test]
+    fn filter_text_and_id() {
+        assert_eq!(ids("vendor id:3c", &sample()), vec!["3C"]);
+        assert_eq!(ids("", &sample()).len(), 3);
+    }
+
+    #[test]
+    fn filter_and_sort_by_postpone_count() {
+        let mut tasks = sample();
+        for (task, times) in tasks.iter_mut().zip([1, 3, 0]) {
+            for _ in 0..times {
+                task.record(crate::models::Action::Postponed, "");
+            }
+        }
+        assert_eq!(ids("postponed:1", &tasks), vec!["1A", "2B"]);
+        assert_eq!(ids("postponed:3", &tasks), vec!["2B"]);
+        assert_eq!(ids("-postponed:1", &tasks), vec!["3C"]);
+        assert!(Filter::parse("postponed:often", &DatePattern::default()).is_err());
+
+        let mut sorted: Vec<&Task> = tasks.iter().collect();
+        sort_tasks(&mut sorted, SortKey::Postponed, false);
+        let ids: Vec<&str> = sorted.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["2B", "1A", "3C"]);
+    }
+
+    #[test]
+    fn filter_rejects_unknown_terms() {
+        assert!(Filter::parse("colour:red", &DatePattern::default()).is_err());
+        assert!(Filter::parse("priority:urgent", &DatePattern::default()).is_err());
+        assert!(Filter::parse("due:someday", &DatePattern::default()).is_err());
+    }
+
+    #[test]
+    fn sort_by_due_puts_undated_last() {
+        let tasks = sample();
+        let mut sorted: Vec<&Task> = tasks.iter().collect();
+        sort_tasks(&mut sorted, "due".parse().unwrap(), true);
+        let ids: Vec<&str> = sorted.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["3C", "2B", "1A"]);
+        sort_tasks(&mut sorted, SortKey::Priority, false);
+        let ids: Vec<&str> = sorted.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["2B", "1A", "3C"]);
+        assert!("size".parse::<SortKey>().is_err());
+    }
+
+    #[test]
+    fn sort_manual_puts_unranked_last() {
+        let mut tasks = sample();
+        for (task, rank) in tasks.iter_mut().zip(["r", "", "9"]) {
+            task.rank = rank.to_string();
+        }
+        let mut sorted: Vec<&Task> = tasks.iter().collect();
+        sort_tasks(&mut sorted, "manual".parse().unwrap(), false);
+        let ids: Vec<&str> = sorted.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["3C", "1A", "2B"]);
+    }
+}
@@ -0,0 +1,334 @@
+use crate::dates::{format_stored_date, parse_stored_date};
+use crate::i18n::{t, tf};
+use crate::models::{Action, Priority, Task};
+use crate::output::TaskRecord;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// The changes hooks are told about. A hook is an executable in the hooks
+// directory whose file name starts with the event name, e.g. `on-add` or
+// `on-complete.chat`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Event {
+    Add,
+    Modify,
+    Complete,
+    Delete,
+}
+
+impl Event {
+    pub fn name(self) -> &'static str {
+        match self {
+            Event::Add => "on-add",
+            Event::Modify => "on-modify",
+            Event::Complete => "on-complete",
+            Event::Delete => "on-delete",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Hooks {
+    dir: Option<PathBuf>,
+    timeout: Duration,
+}
+
+// What a hook reads on stdin.
+#[derive(Serialize)]
+struct HookInput<'a> {
+    event: &'static str,
+    before: Option<TaskRecord<'a>>,
+    after: TaskRecord<'a>,
+}
+
+// What a hook may print on stdout to change the task. Fields it leaves out
+// are kept; the ID, status and history belong to todo_cli and are ignored.
+#[derive(Deserialize)]
+struct HookOutput {
+    description: Option<String>,
+    due_date: Option<String>,
+    priority: Option<String>,
+    notes: Option<String>,
+    tags: Option<Vec<String>>,
+}
+
+impl Hooks {
+    pub fn new(dir: Option<PathBuf>, timeout: Duration) -> Hooks {
+        Hooks { dir, timeout }
+    }
+
+    // Sorted by file name, so hooks can be ordered with a numeric suffix. A
+    // missing hooks directory simply means there are no hooks.
+    fn scripts(&self, event: Event) -> Vec<PathBuf> {
+        let Some(entries) = self.dir.as_ref().and_then(|dir| fs::read_dir(dir).ok()) else {
+            return Vec::new();
+        };
+        let mut scripts: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| script_name(path).starts_with(event.name()) && is_executable(path))
+            .collect();
+        scripts.sort();
+        scripts
+    }
+
+    // Runs every hook for `event` in turn, each seeing the task as the
+    // previous one left it. Returns the task to store, or why a hook refused
+    // the change.
+    pub fn run(
+        &self,
+        event: Event,
+        before: Option<&Task>,
+        mut after: Task,
+    ) -> Result<Task, String> {
+        for script in self.scripts(event) {
+            let input = HookInput {
+                event: event.name(),
+                before: before.map(TaskRecord::from),
+                after: TaskRecord::from(&after),
+            };
+            let input = serde_json::to_string(&input).map_err(|e| e.to_string())?;
+            let output = run_script(&script, &input, self.timeout)?;
+            apply_output(&mut after, &output).map_err(|e| {
+                tf(
+                    "hook_failed",
+                    &[("hook", &script_name(&script)), ("error", &e)],
+                )
+            })?;
+        }
+        Ok(after)
+    }
+}
+
+fn script_name(path: &Path) -> &str {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+}
+
+#[cfg(unix)]
+pub fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+pub fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut text);
+        }
+        text
+    })
+}
+
+// Feeds `input` to the hook and returns what it printed. A non-zero exit
+// vetoes the change, with the hook's first line of output as the reason.
+fn run_script(path: &Path, input: &str, timeout: Duration) -> Result<String, String> {
+    let name = script_name(path);
+    let mut child = Command::new(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| tf("hook_not_run", &[("hook", &name), ("error", &e)]))?;
+
+    // Writing and reading happen on their own threads so a hook that ignores
+    // its input or floods its output still runs into the timeout.
+    let stdin = child.stdin.take();
+    let input = input.to_string();
+    thread::spawn(move || {
+        if let Some(mut stdin) = stdin {
+            let _ = stdin.write_all(input.as_bytes());
+        }
+    });
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(10)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(tf(
+                    "hook_timed_out",
+                    &[("hook", &name), ("seconds", &timeout.as_secs())],
+                ));
+            }
+            Err(e) => return Err(tf("hook_not_run", &[("hook", &name), ("error", &e)])),
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if status.success() {
+        return Ok(stdout);
+    }
+    let reason = [&stderr, &stdout]
+        .into_iter()
+        .find_map(|text| text.lines().map(str::trim).find(|line| !line.is_empty()));
+    Err(match reason {
+        Some(reason) => tf(
+            "hook_rejected_because",
+            &[("hook", &name), ("reason", &reason)],
+        ),
+        None => tf("hook_rejected", &[("hook", &name)]),
+    })
+}
+
+// Applies the fields a hook printed and records them as an edit. Empty
+// output leaves the task alone.
+fn apply_output(task: &mut Task, output: &str) -> Result<(), String> {
+    if output.trim().is_empty() {
+        return Ok(());
+    }
+    let output: HookOutput =
+        serde_json::from_str(output).map_err(|e| tf("hook_invalid_json", &[("error", &e)]))?;
+
+    let mut changed = Vec::new();
+    if let Some(description) = output.description {
+        if description.trim().is_empty() {
+            return Err(t("hook_empty_description").to_string());
+        }
+        if description != task.description {
+            task.description = description;
+            changed.push("description");
+        }
+    }
+    if let Some(due_date) = output.due_date {
+        let due_date = if due_date.is_empty() {
+            due_date
+        } else {
+            parse_stored_date(&due_date)
+                .map(format_stored_date)
+                .ok_or_else(|| tf("hook_invalid_due_date", &[("value", &due_date)]))?
+        };
+        if due_date != task.due_date {
+            task.due_date = due_date;
+            changed.push("due_date");
+        }
+    }
+    if let Some(priority) = output.priority {
+        let priority = Priority::from_name(&priority)
+            .ok_or_else(|| tf("hook_invalid_priority", &[("value", &priority)]))?;
+        if priority != task.priority {
+            task.priority = priority;
+            changed.push("priority");
+        }
+    }
+    if let Some(notes) = output.notes {
+        if notes != task.notes {
+            task.notes = notes;
+            changed.push("notes");
+        }
+    }
+    if let Some(tags) = output.tags {
+        let tags = Task::parse_tags(&tags.join(","));
+        if tags != task.tags {
+            task.tags = tags;
+            changed.push("tags");
+        }
+    }
+
+    if !changed.is_empty() {
+        task.record(Action::Edited, changed.join(","));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task() -> Task {
+        Task {
+            id: "1A".to_string(),
+            description: "Call the vendor".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn output_changes_known_fields() {
+        let mut task = task();
+        let output = r#"{"id": "FF", "priority": "asap", "due_date": "2026-11-02", "tags": ["chat", "Chat"]}"#;
+        apply_output(&mut task, output).unwrap();
+        assert_eq!(task.id, "1A");
+        assert_eq!(task.priority, Priority::Asap);
+        assert_eq!(task.due_date, "2026-11-02");
+        assert_eq!(task.tags, vec!["chat"]);
+        assert_eq!(task.history[0].detail, "due_date,priority,tags");
+
+        assert!(apply_output(&mut task, "").is_ok());
+        assert!(apply_output(&mut task, "not json").is_err());
+        assert!(apply_output(&mut task, r#"{"priority": "urgent"}"#).is_err());
+        assert!(apply_output(&mut task, r#"{"description": " "}"#).is_err());
+        assert_eq!(task.history.len(), 1);
+    }
+
+    #[cfg(unix)]
+    fn hooks_with(scripts: &[(&str, &str)]) -> Hooks {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!(
+            "todo_cli_hooks_{}_{}",
+            std::process::id(),
+            scripts[0].0
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (name, body) in scripts {
+            let path = dir.join(name);
+            fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        Hooks::new(Some(dir), Duration::from_secs(2))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hooks_modify_and_veto_in_order() {
+        let hooks = hooks_with(&[
+            (
+                "on-add.1",
+                r#"grep -q '"event":"on-add"' && echo '{"tags": ["new"]}'"#,
+            ),
+            ("on-add.2", r#"grep -q '"tags":\["new"\]' || exit 1"#),
+            ("on-delete", "echo 'Deleting is not allowed' >&2; exit 3"),
+        ]);
+        let added = hooks.run(Event::Add, None, task()).unwrap();
+        assert_eq!(added.tags, vec!["new"]);
+
+        let unchanged = hooks.run(Event::Modify, Some(&task()), task()).unwrap();
+        assert_eq!(unchanged, task());
+
+        assert_eq!(
+            hooks.run(Event::Delete, Some(&task()), task()),
+            Err("Hook on-delete rejected the change: Deleting is not allowed".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn slow_hooks_time_out() {
+        let mut hooks = hooks_with(&[("on-complete", "sleep 5")]);
+        hooks.timeout = Duration::from_millis(100);
+        let err = hooks
+            .run(Event::Complete, Some(&task()), task())
+            .unwrap_err();
+        assert!(err.contains("timed out"), "{err}");
+    }
+}
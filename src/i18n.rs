@@ -0,0 +1,1135 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Language {
+    #[default]
+    En,
+    De,
+}
+
+pub const LANGUAGES: [Language; 2] = [Language::En, Language::De];
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "en" | "english" => Ok(Language::En),
+            "de" | "german" | "deutsch" => Ok(Language::De),
+            other => Err(format!(
+                "Unknown language '{other}'. Expected auto, en or de."
+            )),
+        }
+    }
+}
+
+impl Language {
+    // Reads the usual locale variables, e.g. `LANG=de_DE.UTF-8`. Anything
+    // without a catalog falls back to English.
+    pub fn from_env() -> Language {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map(|value| Language::from_locale(&value))
+            .unwrap_or_default()
+    }
+
+    fn from_locale(locale: &str) -> Language {
+        let code = locale.split(['_', '.', '@']).next().unwrap_or_default();
+        code.parse().unwrap_or_default()
+    }
+}
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+// Called once at startup; later calls are ignored.
+pub fn set_language(language: Language) {
+    let _ = LANGUAGE.set(language);
+}
+
+pub fn language() -> Language {
+    LANGUAGE.get().copied().unwrap_or_default()
+}
+
+// Each entry is (key, English, German). Placeholders are written `{name}` and
+// filled in by `tf`.
+const MESSAGES: &[(&str, &str, &str)] = &[
+    ("priority_asap", "ASAP", "Sofort"),
+    ("priority_important", "Important", "Wichtig"),
+    ("priority_medium", "Medium", "Mittel"),
+    ("priority_minor", "Minor", "Gering"),
+    ("priority_none", "None", "Keine"),
+    ("yes_answers", "y,yes", "j,ja,y,yes"),
+    (
+        "usage",
+        "Usage: todo_cli <new|clone|view|show|next|explain|agenda|calendar|stats|done|edit|snooze|reschedule|move|remind|notify|schedule|delete|trash|template|tui|shell|config|help> [--format FORMAT] [--color WHEN]",
+        "Aufruf: todo_cli <new|clone|view|show|next|explain|agenda|calendar|stats|done|edit|snooze|reschedule|move|remind|notify|schedule|delete|trash|template|tui|shell|config|help> [--format FORMAT] [--color WANN]",
+    ),
+    ("usage_edit_editor", "Usage: todo_cli edit <ID> --editor", "Aufruf: todo_cli edit <ID> --editor"),
+    (
+        "usage_trash_restore",
+        "Usage: todo_cli trash restore <ID...>",
+        "Aufruf: todo_cli trash restore <ID...>",
+    ),
+    ("usage_config_get", "Usage: todo_cli config get <KEY>", "Aufruf: todo_cli config get <SCHLÜSSEL>"),
+    (
+        "usage_config_set",
+        "Usage: todo_cli config set <KEY> <VALUE> [--local]",
+        "Aufruf: todo_cli config set <SCHLÜSSEL> <WERT> [--local]",
+    ),
+    ("error_saving", "Error saving tasks: {error}", "Fehler beim Speichern der Aufgaben: {error}"),
+    ("error_reading_file", "Could not read {file}: {error}", "{file} konnte nicht gelesen werden: {error}"),
+    (
+        "warning_bad_history",
+        "Skipping a history entry of task {id}: {error}",
+        "Ein Verlaufseintrag von Aufgabe {id} wird übersprungen: {error}",
+    ),
+    (
+        "error_assigning_ids",
+        "Error assigning task IDs: {error}",
+        "Fehler beim Vergeben der Aufgaben-IDs: {error}",
+    ),
+    ("error_creating", "Error creating task: {error}", "Fehler beim Anlegen der Aufgabe: {error}"),
+    ("error_reading_input", "Error reading input: {error}", "Fehler beim Lesen der Eingabe: {error}"),
+    ("no_tasks_found", "No tasks found.", "Keine Aufgaben gefunden."),
+    ("no_matching_tasks", "No matching tasks.", "Keine passenden Aufgaben."),
+    ("no_tasks_to_complete", "No tasks to complete.", "Keine Aufgaben zum Erledigen."),
+    ("no_tasks_to_edit", "No tasks to edit.", "Keine Aufgaben zum Bearbeiten."),
+    ("no_tasks_to_delete", "No tasks to delete.", "Keine Aufgaben zum Löschen."),
+    ("task_not_found", "Task ID {id} not found.", "Aufgabe mit der ID {id} nicht gefunden."),
+    ("invalid_date", "Invalid date '{input}'.", "Ungültiges Datum '{input}'."),
+    (
+        "invalid_date_format",
+        "Invalid date. Please use {format} format.",
+        "Ungültiges Datum. Bitte das Format {format} verwenden.",
+    ),
+    ("invalid_weeks", "Invalid number of weeks '{input}'.", "Ungültige Anzahl Wochen '{input}'."),
+    (
+        "invalid_age",
+        "Invalid age '{input}'. Use a number above zero followed by h, d or w, e.g. 30d.",
+        "Ungültiges Alter '{input}'. Bitte eine Zahl größer als null gefolgt von h, d oder w angeben, z. B. 30d.",
+    ),
+    ("cancelled", "Cancelled.", "Abgebrochen."),
+    ("description_empty", "Description cannot be empty.", "Die Beschreibung darf nicht leer sein."),
+    ("prompt_description", "Enter task description: ", "Beschreibung der Aufgabe eingeben: "),
+    ("prompt_due_date", "Enter task due date ({format}): ", "Fälligkeitsdatum eingeben ({format}): "),
+    ("prompt_priority", "Select a priority:", "Priorität wählen:"),
+    (
+        "priority_retry_default",
+        "Please enter a number 1-5 or press Enter for {priority}.",
+        "Bitte eine Zahl von 1 bis 5 eingeben oder Enter für {priority} drücken.",
+    ),
+    ("prompt_notes", "Enter notes (optional): ", "Notizen eingeben (optional): "),
+    (
+        "prompt_tags",
+        "Enter tags (optional, comma-separated): ",
+        "Schlagwörter eingeben (optional, durch Kommas getrennt): ",
+    ),
+    ("picker_complete", "Complete a task", "Aufgabe erledigen"),
+    ("picker_edit", "Edit a task", "Aufgabe bearbeiten"),
+    ("picker_show", "Show a task", "Aufgabe anzeigen"),
+    ("usage_show", "Usage: todo_cli show <ID>", "Aufruf: todo_cli show <ID>"),
+    ("usage_explain", "Usage: todo_cli explain <ID>", "Aufruf: todo_cli explain <ID>"),
+    ("urgency_score", "Urgency: {score}", "Dringlichkeit: {score}"),
+    ("urgency_of", "Urgency of {id}: {score}", "Dringlichkeit von {id}: {score}"),
+    (
+        "prompt_complete_id",
+        "Enter the task ID of the finished task: ",
+        "ID der erledigten Aufgabe eingeben: ",
+    ),
+    ("completed", "Completed: {description}", "Erledigt: {description}"),
+    ("edit_description", "Description [{value}]: ", "Beschreibung [{value}]: "),
+    ("edit_due_date", "Due date [{value}]: ", "Fällig am [{value}]: "),
+    ("edit_priority", "Priority [{value}]:", "Priorität [{value}]:"),
+    (
+        "edit_priority_prompt",
+        "Enter 1-5 or press Enter to keep current: ",
+        "1-5 eingeben oder Enter drücken, um den Wert zu behalten: ",
+    ),
+    (
+        "priority_retry_keep",
+        "Please enter a number 1-5 or press Enter to keep current.",
+        "Bitte eine Zahl von 1 bis 5 eingeben oder Enter drücken, um den Wert zu behalten.",
+    ),
+    ("edit_notes", "Notes [{value}]: ", "Notizen [{value}]: "),
+    ("edit_tags", "Tags [{value}]: ", "Schlagwörter [{value}]: "),
+    ("postponed_times", "Postponed {count} time(s)", "{count}-mal verschoben"),
+    ("task_updated", "Task[{id}] updated:\n{task}", "Aufgabe[{id}] aktualisiert:\n{task}"),
+    ("confirm_complete", "Complete {count} task(s)? [y/N]: ", "{count} Aufgabe(n) erledigen? [j/N]: "),
+    ("confirm_update", "Update {count} task(s)? [y/N]: ", "{count} Aufgabe(n) aktualisieren? [j/N]: "),
+    ("confirm_snooze", "Snooze {count} task(s)? [y/N]: ", "{count} Aufgabe(n) zurückstellen? [j/N]: "),
+    (
+        "confirm_reschedule",
+        "Reschedule {count} task(s)? [y/N]: ",
+        "{count} Aufgabe(n) verschieben? [j/N]: ",
+    ),
+    ("confirm_delete", "Delete {count} task(s)? [y/N]: ", "{count} Aufgabe(n) löschen? [j/N]: "),
+    (
+        "bulk_edit_requires_set",
+        "Editing several tasks at once requires at least one --set FIELD=VALUE.",
+        "Um mehrere Aufgaben auf einmal zu bearbeiten, ist mindestens ein --set FELD=WERT nötig.",
+    ),
+    (
+        "usage_snooze",
+        "Usage: todo_cli snooze <ID...|--overdue|--filter EXPR> <WHEN>",
+        "Aufruf: todo_cli snooze <ID...|--overdue|--filter AUSDRUCK> <WANN>",
+    ),
+    (
+        "usage_reschedule",
+        "Usage: todo_cli reschedule <ID...|--filter EXPR> --shift OFFSET",
+        "Aufruf: todo_cli reschedule <ID...|--filter AUSDRUCK> --shift VERSATZ",
+    ),
+    (
+        "shift_needs_offset",
+        "--shift expects an offset such as +1w or -2d, not '{input}'.",
+        "--shift erwartet einen Versatz wie +1w oder -2d, nicht '{input}'.",
+    ),
+    ("rescheduled", "Due {due}: {description} [{id}]", "Fällig am {due}: {description} [{id}]"),
+    (
+        "skipped_undated",
+        "Skipped {count} task(s) without a due date.",
+        "{count} Aufgabe(n) ohne Fälligkeitsdatum übersprungen.",
+    ),
+    (
+        "moved_to_trash",
+        "Moved to trash: {description} [{id}]",
+        "In den Papierkorb verschoben: {description} [{id}]",
+    ),
+    ("trash_empty", "The trash is empty.", "Der Papierkorb ist leer."),
+    (
+        "not_in_trash",
+        "Task ID {id} is not in the trash.",
+        "Aufgaben-ID {id} ist nicht im Papierkorb.",
+    ),
+    ("restored", "Restored: {description} [{id}]", "Wiederhergestellt: {description} [{id}]"),
+    (
+        "restored_new_id",
+        "Restored: {description} [{id}] (ID {old_id} was in use)",
+        "Wiederhergestellt: {description} [{id}] (ID {old_id} war vergeben)",
+    ),
+    (
+        "confirm_empty_trash",
+        "Permanently remove trashed tasks? This cannot be undone. [y/N]: ",
+        "Aufgaben im Papierkorb endgültig entfernen? Das lässt sich nicht rückgängig machen. [j/N]: ",
+    ),
+    (
+        "trash_emptied",
+        "Permanently removed {count} task(s) from the trash.",
+        "{count} Aufgabe(n) endgültig aus dem Papierkorb entfernt.",
+    ),
+    (
+        "unknown_trash_command",
+        "Unknown trash command '{command}'. Expected list, restore or empty.",
+        "Unbekannter Papierkorb-Befehl '{command}'. Möglich sind list, restore oder empty.",
+    ),
+    (
+        "no_config_dir",
+        "Could not locate the config directory; set HOME or use --local.",
+        "Das Konfigurationsverzeichnis wurde nicht gefunden; bitte HOME setzen oder --local verwenden.",
+    ),
+    (
+        "no_templates_dir",
+        "Could not locate the config directory for templates; set HOME.",
+        "Das Konfigurationsverzeichnis für Vorlagen wurde nicht gefunden; bitte HOME setzen.",
+    ),
+    (
+        "usage_template_save",
+        "Usage: todo_cli template save <NAME> <ID> [--due OFFSET] [--replace]",
+        "Aufruf: todo_cli template save <NAME> <ID> [--due VERSATZ] [--replace]",
+    ),
+    (
+        "usage_template_delete",
+        "Usage: todo_cli template delete <NAME>",
+        "Aufruf: todo_cli template delete <NAME>",
+    ),
+    (
+        "unknown_template_command",
+        "Unknown template command '{command}'. Expected list, save or delete.",
+        "Unbekannter template-Befehl '{command}'. Möglich sind list, save oder delete.",
+    ),
+    ("template_saved", "Saved template {name} to {path}", "Vorlage {name} gespeichert in {path}"),
+    (
+        "usage_clone",
+        "Usage: todo_cli clone <ID> [--due DATE|OFFSET] [--set FIELD=VALUE]",
+        "Aufruf: todo_cli clone <ID> [--due DATUM|VERSATZ] [--set FELD=WERT]",
+    ),
+    (
+        "usage_move",
+        "Usage: todo_cli move <ID> <--top|--bottom|--before ID|--after ID>",
+        "Aufruf: todo_cli move <ID> <--top|--bottom|--before ID|--after ID>",
+    ),
+    (
+        "move_self",
+        "Task {id} cannot be moved relative to itself.",
+        "Aufgabe {id} kann nicht relativ zu sich selbst verschoben werden.",
+    ),
+    (
+        "moved",
+        "Moved {id}: {description}",
+        "{id} verschoben: {description}",
+    ),
+    (
+        "no_reminders",
+        "No reminders to come.",
+        "Keine anstehenden Erinnerungen.",
+    ),
+    ("reminder_due", "Task {id}, due {due}", "Aufgabe {id}, fällig am {due}"),
+    ("reminder_undated", "Task {id}", "Aufgabe {id}"),
+    (
+        "error_notifying",
+        "Could not show the reminder for {id}: {error}",
+        "Die Erinnerung für {id} konnte nicht angezeigt werden: {error}",
+    ),
+    (
+        "usage_notify",
+        "Usage: todo_cli notify <ID>",
+        "Aufruf: todo_cli notify <ID>",
+    ),
+    (
+        "usage_schedule",
+        "Usage: todo_cli schedule export <--cron|--systemd> [--file PATH]",
+        "Aufruf: todo_cli schedule export <--cron|--systemd> [--file PFAD]",
+    ),
+    (
+        "scheduled_cron",
+        "Scheduled {count} reminder(s) in {target}.",
+        "{count} Erinnerung(en) in {target} eingeplant.",
+    ),
+    (
+        "schedule_unchanged",
+        "{target} is up to date with {count} reminder(s).",
+        "{target} ist mit {count} Erinnerung(en) aktuell.",
+    ),
+    (
+        "scheduled_systemd",
+        "Scheduled {count} reminder(s) in {dir}: {written} unit file(s) written, {removed} removed.",
+        "{count} Erinnerung(en) in {dir} eingeplant: {written} Unit-Datei(en) geschrieben, {removed} entfernt.",
+    ),
+    (
+        "schedule_apply",
+        "To apply the changes, run:",
+        "Zum Übernehmen der Änderungen ausführen:",
+    ),
+    (
+        "no_systemd_dir",
+        "Cannot find the systemd user directory. Set HOME or pass --file DIR.",
+        "Das systemd-Benutzerverzeichnis wurde nicht gefunden. HOME setzen oder --file VERZEICHNIS angeben.",
+    ),
+    (
+        "error_scheduling",
+        "Could not export the schedule: {error}",
+        "Der Zeitplan konnte nicht exportiert werden: {error}",
+    ),
+    (
+        "cloned",
+        "Cloned {old_id} as {id}: {description}",
+        "{old_id} kopiert als {id}: {description}",
+    ),
+    ("template_deleted", "Deleted template {name}", "Vorlage {name} gelöscht"),
+    (
+        "no_templates",
+        "No templates yet. Save one with 'template save NAME ID'.",
+        "Noch keine Vorlagen. Mit 'template save NAME ID' eine speichern.",
+    ),
+    ("prompt_placeholder", "{name}:", "{name}:"),
+    ("shell_nested", "Already in the shell.", "Die Shell läuft bereits."),
+    (
+        "shell_reloaded",
+        "Reloaded {file} after it changed on disk.",
+        "{file} wurde nach einer Änderung neu geladen.",
+    ),
+    (
+        "shell_reload_failed",
+        "{file} changed on disk but cannot be read, so no changes are saved until it can. Fix the file and try again.",
+        "{file} wurde geändert, kann aber nicht gelesen werden. Bis das wieder geht, wird nichts gespeichert. Die Datei reparieren und erneut versuchen.",
+    ),
+    ("config_set", "Set {key} = {value} in {path}", "{key} = {value} in {path} gesetzt"),
+    (
+        "unknown_config_command",
+        "Unknown config command '{command}'. Use list, get or set.",
+        "Unbekannter config-Befehl '{command}'. Möglich sind list, get oder set.",
+    ),
+    (
+        "help",
+        "New:  Create a new task
+Clone ID: Copy a task under a new ID (--due DATE|OFFSET, --set FIELD=VALUE to change the copy)
+Edit: Modify an existing task (--set FIELD=VALUE to edit without prompts)
+  New and Edit accept --editor to write the task in $VISUAL or $EDITOR
+Done: Mark a task as complete
+Delete: Move a task to the trash
+  Done, Edit and Delete accept several IDs or --filter EXPR; pass --yes to skip confirmation
+Snooze <ID...|--overdue> WHEN: Postpone tasks, e.g. by +2d or until tomorrow
+Reschedule <ID...|--filter EXPR> --shift OFFSET: Move due dates, e.g. by +1w or -3d
+Move ID <--top|--bottom|--before ID|--after ID>: Change a task's place in the manual order (view --sort manual)
+Remind [--check|--daemon]: List reminders, show due ones once, or keep watching for them (edit --set remind=-1h)
+Notify ID: Show a task's reminder now unless it already fired
+Schedule export <--cron|--systemd>: Write crontab lines or systemd timers that run notify for each reminder (--file PATH)
+Trash <list|restore ID|empty>: Manage deleted tasks (--older-than 30d for empty)
+Template <list|save NAME ID|delete NAME>: Manage task templates; New --template NAME uses one
+View: Display all tasks (--sort due|priority|description|created|postponed|urgency|manual)
+Show ID: Display every field and the history of a task, including completed and deleted ones
+Next [TERMS]: Display the most urgent open task
+Explain ID: Break a task's urgency score down term by term
+Agenda: Display tasks grouped by when they are due
+Calendar [MM/YYYY]: Display a month grid of due tasks (--week for a week view)
+Stats: Report open, overdue and completed task statistics
+Tui: Browse and triage tasks in a full-screen view
+Shell: Run several commands in one interactive session
+Config <list|get KEY|set KEY VALUE>: Show or change settings (--local for this directory)
+Help: Show this help message
+Any other NAME runs the plugin todo_cli-NAME from your PATH",
+        "New:  Neue Aufgabe anlegen
+Clone ID: Aufgabe unter neuer ID kopieren (--due DATUM|VERSATZ, --set FELD=WERT ändert die Kopie)
+Edit: Aufgabe ändern (--set FELD=WERT ändert ohne Rückfragen)
+  New und Edit akzeptieren --editor, um die Aufgabe in $VISUAL oder $EDITOR zu schreiben
+Done: Aufgabe als erledigt markieren
+Delete: Aufgabe in den Papierkorb verschieben
+  Done, Edit und Delete akzeptieren mehrere IDs oder --filter AUSDRUCK; --yes überspringt die Rückfrage
+Snooze <ID...|--overdue> WANN: Aufgaben zurückstellen, z. B. um +2d oder bis tomorrow
+Reschedule <ID...|--filter AUSDRUCK> --shift VERSATZ: Fälligkeiten verschieben, z. B. um +1w oder -3d
+Move ID <--top|--bottom|--before ID|--after ID>: Platz einer Aufgabe in der manuellen Reihenfolge ändern (view --sort manual)
+Remind [--check|--daemon]: Erinnerungen auflisten, fällige einmal anzeigen oder laufend darauf achten (edit --set remind=-1h)
+Notify ID: Die Erinnerung einer Aufgabe jetzt anzeigen, sofern sie noch nicht kam
+Schedule export <--cron|--systemd>: Crontab-Zeilen oder systemd-Timer schreiben, die notify für jede Erinnerung starten (--file PFAD)
+Trash <list|restore ID|empty>: Gelöschte Aufgaben verwalten (--older-than 30d für empty)
+Template <list|save NAME ID|delete NAME>: Aufgabenvorlagen verwalten; New --template NAME verwendet eine
+View: Alle Aufgaben anzeigen (--sort due|priority|description|created|postponed|urgency|manual)
+Show ID: Alle Felder und den Verlauf einer Aufgabe anzeigen, auch erledigter und gelöschter
+Next [BEGRIFFE]: Die dringendste offene Aufgabe anzeigen
+Explain ID: Die Dringlichkeit einer Aufgabe Posten für Posten aufschlüsseln
+Agenda: Aufgaben nach Fälligkeit gruppiert anzeigen
+Calendar [MM/JJJJ]: Monatsansicht der fälligen Aufgaben (--week für eine Wochenansicht)
+Stats: Statistik über offene, überfällige und erledigte Aufgaben
+Tui: Aufgaben in einer Vollbildansicht durchsehen und bearbeiten
+Shell: Mehrere Befehle in einer interaktiven Sitzung ausführen
+Config <list|get SCHLÜSSEL|set SCHLÜSSEL WERT>: Einstellungen anzeigen oder ändern (--local für dieses Verzeichnis)
+Help: Diese Hilfe anzeigen
+Jeder andere NAME startet das Plugin todo_cli-NAME aus dem PATH",
+    ),
+    ("field_id", "ID", "ID"),
+    ("field_description", "Description", "Beschreibung"),
+    ("field_status", "Status", "Status"),
+    ("field_priority", "Priority", "Priorität"),
+    ("field_due", "Due", "Fällig"),
+    ("field_tags", "Tags", "Schlagwörter"),
+    ("field_remind", "Reminder", "Erinnerung"),
+    ("field_postponed", "Postponed", "Verschoben"),
+    ("field_created", "Created", "Angelegt"),
+    ("field_modified", "Modified", "Geändert"),
+    ("field_completed", "Completed", "Erledigt"),
+    ("field_deleted", "Deleted", "Gelöscht"),
+    ("field_notes", "Notes", "Notizen"),
+    ("field_history", "History", "Verlauf"),
+    ("status_open", "Open", "Offen"),
+    ("status_done", "Done", "Erledigt"),
+    ("status_deleted", "Deleted", "Gelöscht"),
+    ("help_exit", "Exit: Leave the shell (or press Ctrl-D)", "Exit: Die Shell verlassen (oder Strg-D drücken)"),
+    ("help_aliases", "Aliases:", "Aliase:"),
+    ("help_reports", "Reports:", "Berichte:"),
+    ("help_plugins", "Plugins:", "Plugins:"),
+    (
+        "error_running_plugin",
+        "Error running plugin {plugin}: {error}",
+        "Fehler beim Ausführen des Plugins {plugin}: {error}",
+    ),
+    ("agenda_overdue", "Overdue", "Überfällig"),
+    ("agenda_today", "Today", "Heute"),
+    ("agenda_tomorrow", "Tomorrow", "Morgen"),
+    ("agenda_this_week", "This Week", "Diese Woche"),
+    ("agenda_next_week", "Next Week", "Nächste Woche"),
+    ("agenda_later", "Later", "Später"),
+    ("agenda_no_date", "No Date", "Ohne Datum"),
+    ("stats_open_by_priority", "Open tasks by priority", "Offene Aufgaben nach Priorität"),
+    ("stats_total", "Total", "Gesamt"),
+    ("stats_overdue", "Overdue", "Überfällig"),
+    ("stats_days", "{days} days", "{days} Tage"),
+    ("stats_not_available", "n/a", "k. A."),
+    ("stats_average_age", "Average open task age", "Alter offener Aufgaben"),
+    ("stats_completed_per_week", "Completed per week", "Erledigt pro Woche"),
+    ("stats_week_of", "Week of {date}", "Woche ab {date}"),
+    ("stats_timeliness", "Completion timeliness", "Pünktlichkeit"),
+    ("stats_on_time", "On time", "Pünktlich"),
+    ("stats_late", "Late", "Verspätet"),
+    ("stats_on_time_rate", "On-time rate", "Pünktlichkeitsquote"),
+    ("tui_updated", "Task[{id}] updated", "Aufgabe[{id}] geändert"),
+    ("tui_created", "Created: {description} [{id}]", "Angelegt: {description} [{id}]"),
+    ("tui_title", "Tasks ({count}) sorted by {sort}", "Aufgaben ({count}) sortiert nach {sort}"),
+    (
+        "tui_title_reversed",
+        "Tasks ({count}) sorted by {sort}, reversed",
+        "Aufgaben ({count}) sortiert nach {sort}, umgekehrt",
+    ),
+    ("tui_details", "Details", "Details"),
+    ("tui_filter", "Filter: {filter}", "Filter: {filter}"),
+    (
+        "tui_keys",
+        "j/k move  d done  e edit  n new  x delete  p priority  / filter  s sort  r reverse  q quit",
+        "j/k bewegen  d erledigt  e ändern  n neu  x löschen  p Priorität  / Filter  s sortieren  r umkehren  q beenden",
+    ),
+    (
+        "tui_needs_terminal",
+        "The tui command needs an interactive terminal.",
+        "Der Befehl tui braucht ein interaktives Terminal.",
+    ),
+    (
+        "picker_title",
+        "{shown}/{total} tasks, Enter to select, Esc to cancel",
+        "{shown}/{total} Aufgaben, Enter wählt aus, Esc bricht ab",
+    ),
+    (
+        "unknown_id_strategy",
+        "Unknown ID strategy '{strategy}'. Expected random or sequential.",
+        "Unbekannte ID-Vergabe '{strategy}'. Erwartet: random oder sequential.",
+    ),
+    (
+        "ids_exhausted",
+        "All 256 task IDs are in use. Cannot create a new task.",
+        "Alle 256 Aufgaben-IDs sind vergeben. Es kann keine neue Aufgabe angelegt werden.",
+    ),
+    (
+        "filter_unknown_priority",
+        "Unknown priority '{value}' in filter.",
+        "Unbekannte Priorität '{value}' im Filter.",
+    ),
+    (
+        "filter_unknown_due",
+        "Unknown due value '{value}' in filter.",
+        "Unbekannte Fälligkeit '{value}' im Filter.",
+    ),
+    (
+        "filter_unknown_status",
+        "Unknown status '{value}' in filter.",
+        "Unbekannter Status '{value}' im Filter.",
+    ),
+    (
+        "filter_invalid_postponed",
+        "Invalid postpone count '{value}' in filter.",
+        "Ungültige Anzahl Verschiebungen '{value}' im Filter.",
+    ),
+    ("filter_unknown_key", "Unknown filter key '{key}'.", "Unbekannter Filterschlüssel '{key}'."),
+    (
+        "unknown_sort_key",
+        "Unknown sort key '{key}'. Expected due, priority, description, created, postponed, urgency or manual.",
+        "Unbekannte Sortierung '{key}'. Erwartet: due, priority, description, created, postponed, urgency oder manual.",
+    ),
+    (
+        "invalid_set",
+        "Invalid --set '{assignment}'. Expected FIELD=VALUE.",
+        "Ungültiges --set '{assignment}'. Erwartet: FELD=WERT.",
+    ),
+    (
+        "invalid_date_value",
+        "Invalid date '{input}'. Please use {format} format.",
+        "Ungültiges Datum '{input}'. Bitte das Format {format} verwenden.",
+    ),
+    (
+        "invalid_priority",
+        "Invalid priority '{value}'. Expected asap, important, medium, minor or none.",
+        "Ungültige Priorität '{value}'. Erwartet: asap, important, medium, minor oder none.",
+    ),
+    ("unknown_set_field", "Unknown field '{field}' in --set.", "Unbekanntes Feld '{field}' in --set."),
+    (
+        "set_field_no_add",
+        "Field '{field}' does not support += or -=.",
+        "Feld '{field}' unterstützt kein += oder -=.",
+    ),
+    ("error_writing_file", "Could not write {file}: {error}", "{file} konnte nicht geschrieben werden: {error}"),
+    ("error_creating_dir", "Could not create {dir}: {error}", "{dir} konnte nicht angelegt werden: {error}"),
+    ("error_removing_file", "Could not remove {file}: {error}", "{file} konnte nicht entfernt werden: {error}"),
+    (
+        "invalid_template_name",
+        "Invalid template name '{name}'. Use letters, digits, '-' and '_'.",
+        "Ungültiger Vorlagenname '{name}'. Erlaubt sind Buchstaben, Ziffern, '-' und '_'.",
+    ),
+    (
+        "invalid_due_offset",
+        "Invalid due offset '{input}'. Expected e.g. +3d, +2w or +1m.",
+        "Ungültiger Fälligkeitsversatz '{input}'. Erwartet z. B. +3d, +2w oder +1m.",
+    ),
+    ("placeholder_unclosed", "Unclosed '{' in '{text}'.", "Nicht geschlossenes '{' in '{text}'."),
+    ("placeholder_empty", "Empty placeholder '{}' in '{text}'.", "Leerer Platzhalter '{}' in '{text}'."),
+    ("placeholder_unmatched", "Unmatched '}' in '{text}'.", "Überzähliges '}' in '{text}'."),
+    ("field_not_string", "Field '{field}' must be a string.", "Feld '{field}' muss ein Text sein."),
+    ("value_not_string", "'{field}' must be a string.", "'{field}' muss ein Text sein."),
+    (
+        "template_tags_not_strings",
+        "'tags' must be an array of strings.",
+        "'tags' muss eine Liste von Texten sein.",
+    ),
+    ("unknown_field", "Unknown field '{field}'.", "Unbekanntes Feld '{field}'."),
+    ("template_no_description", "'description' is missing.", "'description' fehlt."),
+    ("no_template", "No template named '{name}'.", "Keine Vorlage namens '{name}'."),
+    ("invalid_template", "Invalid template {file}: {error}", "Ungültige Vorlage {file}: {error}"),
+    (
+        "template_exists",
+        "Template '{name}' already exists. Pass --replace to overwrite it.",
+        "Vorlage '{name}' gibt es schon. Mit --replace wird sie überschrieben.",
+    ),
+    ("hook_failed", "Hook {hook}: {error}", "Hook {hook}: {error}"),
+    ("hook_not_run", "Could not run hook {hook}: {error}", "Hook {hook} konnte nicht gestartet werden: {error}"),
+    (
+        "hook_timed_out",
+        "Hook {hook} timed out after {seconds}s.",
+        "Hook {hook} wurde nach {seconds}s abgebrochen.",
+    ),
+    (
+        "hook_rejected_because",
+        "Hook {hook} rejected the change: {reason}",
+        "Hook {hook} hat die Änderung abgelehnt: {reason}",
+    ),
+    ("hook_rejected", "Hook {hook} rejected the change.", "Hook {hook} hat die Änderung abgelehnt."),
+    ("hook_invalid_json", "Printed invalid JSON: {error}", "Ungültiges JSON ausgegeben: {error}"),
+    ("hook_empty_description", "Left the description empty.", "Beschreibung leer gelassen."),
+    (
+        "hook_invalid_due_date",
+        "Printed an invalid due date '{value}'.",
+        "Ungültiges Fälligkeitsdatum '{value}' ausgegeben.",
+    ),
+    (
+        "hook_invalid_priority",
+        "Printed an invalid priority '{value}'.",
+        "Ungültige Priorität '{value}' ausgegeben.",
+    ),
+    ("error_running", "Could not run {program}: {error}", "{program} konnte nicht gestartet werden: {error}"),
+    ("command_failed", "{program} failed: {error}", "{program} ist fehlgeschlagen: {error}"),
+    ("unit_description", "Reminder for {id}: {description}", "Erinnerung an {id}: {description}"),
+    (
+        "crontab_unterminated",
+        "The crontab has a '{marker}' line but no '{end}' line. Fix the crontab by hand and export again.",
+        "Die Crontab enthält eine Zeile '{marker}', aber keine Zeile '{end}'. Korrigieren Sie die Crontab von Hand und exportieren Sie erneut.",
+    ),
+    (
+        "invalid_reminder",
+        "Invalid reminder '{input}'. Expected an offset from the due time such as -1h or -2d, or a date in {format} format and a time such as 09:00.",
+        "Ungültige Erinnerung '{input}'. Erwartet wird ein Versatz zur Fälligkeit wie -1h oder -2d oder ein Datum im Format {format} mit einer Uhrzeit wie 09:00.",
+    ),
+    (
+        "reminder_offset_too_large",
+        "Invalid reminder '{input}'. A reminder can be at most {days} days from the due time.",
+        "Ungültige Erinnerung '{input}'. Eine Erinnerung darf höchstens {days} Tage von der Fälligkeit entfernt sein.",
+    ),
+    ("reminder_command_empty", "The reminder command is empty.", "Der Erinnerungsbefehl ist leer."),
+    ("command_exited", "{program} exited with {status}: {error}", "{program} endete mit {status}: {error}"),
+    ("weekday_mon", "Mon", "Mo"),
+    ("weekday_tue", "Tue", "Di"),
+    ("weekday_wed", "Wed", "Mi"),
+    ("weekday_thu", "Thu", "Do"),
+    ("weekday_fri", "Fri", "Fr"),
+    ("weekday_sat", "Sat", "Sa"),
+    ("weekday_sun", "Sun", "So"),
+    ("month_january", "January", "Januar"),
+    ("month_february", "February", "Februar"),
+    ("month_march", "March", "März"),
+    ("month_april", "April", "April"),
+    ("month_may", "May", "Mai"),
+    ("month_june", "June", "Juni"),
+    ("month_july", "July", "Juli"),
+    ("month_august", "August", "August"),
+    ("month_september", "September", "September"),
+    ("month_october", "October", "Oktober"),
+    ("month_november", "November", "November"),
+    ("month_december", "December", "Dezember"),
+    (
+        "invalid_month",
+        "Invalid month '{input}'. Please use MM/YYYY format.",
+        "Ungültiger Monat '{input}'. Bitte das Format MM/JJJJ verwenden.",
+    ),
+    ("database_path_empty", "The database path cannot be empty.", "Der Datenbankpfad darf nicht leer sein."),
+    (
+        "invalid_separator_width",
+        "Invalid separator width '{value}'. Expected a number from 1 to 200.",
+        "Ungültige Trennlinienbreite '{value}'. Erwartet wird eine Zahl von 1 bis 200.",
+    ),
+    (
+        "invalid_urgency_weight",
+        "Invalid urgency weight '{value}'. Expected a number.",
+        "Ungültiges Dringlichkeitsgewicht '{value}'. Erwartet wird eine Zahl.",
+    ),
+    (
+        "invalid_hook_timeout",
+        "Invalid hook timeout '{value}'. Expected a number of seconds from 1 to 3600.",
+        "Ungültige Hook-Zeitgrenze '{value}'. Erwartet wird eine Sekundenzahl von 1 bis 3600.",
+    ),
+    (
+        "invalid_time",
+        "Invalid time '{value}'. Expected HH:MM, e.g. 09:00.",
+        "Ungültige Uhrzeit '{value}'. Erwartet: HH:MM, z. B. 09:00.",
+    ),
+    (
+        "invalid_interval",
+        "Invalid interval '{value}'. Expected a number of seconds from 1 to 3600.",
+        "Ungültiges Intervall '{value}'. Erwartet wird eine Sekundenzahl von 1 bis 3600.",
+    ),
+    (
+        "reminder_command_blank",
+        "The reminder command cannot be empty. Use 'auto' for the default.",
+        "Der Erinnerungsbefehl darf nicht leer sein. 'auto' steht für die Voreinstellung.",
+    ),
+    (
+        "unknown_setting",
+        "Unknown setting '{key}'. Run 'todo_cli config list' to see all settings.",
+        "Unbekannte Einstellung '{key}'. 'todo_cli config list' zeigt alle Einstellungen.",
+    ),
+    (
+        "builtin_command_name",
+        "'{name}' is a built-in command and cannot be redefined.",
+        "'{name}' ist ein eingebauter Befehl und kann nicht neu definiert werden.",
+    ),
+    ("invalid_command_name", "'{name}' is not a valid command name.", "'{name}' ist kein gültiger Befehlsname."),
+    (
+        "setting_global_only",
+        "Setting '{key}' runs a command, so it can only be set in the global config file, not in {file}.",
+        "Einstellung '{key}' startet einen Befehl und kann daher nur in der globalen Konfiguration gesetzt werden, nicht in {file}.",
+    ),
+    ("invalid_config_file", "Invalid config file {file}: {error}", "Ungültige Konfigurationsdatei {file}: {error}"),
+    ("unknown_setting_in", "Unknown setting '{key}' in {file}.", "Unbekannte Einstellung '{key}' in {file}."),
+    (
+        "setting_not_scalar",
+        "Setting '{key}' in {file} must be a string or a number.",
+        "Einstellung '{key}' in {file} muss ein Text oder eine Zahl sein.",
+    ),
+    ("in_setting", "{error} (setting '{key}' in {file})", "{error} (Einstellung '{key}' in {file})"),
+    (
+        "alias_unknown_command",
+        "Alias '{name}' expands to unknown command '{command}'. Aliases must start with a built-in command or a report.",
+        "Alias '{name}' wird zum unbekannten Befehl '{command}'. Aliase müssen mit einem eingebauten Befehl oder einem Bericht beginnen.",
+    ),
+    (
+        "alias_and_report",
+        "'{name}' is defined both as an alias and as a report.",
+        "'{name}' ist sowohl als Alias als auch als Bericht definiert.",
+    ),
+    ("not_a_table", "'{name}' in {file} must be a table.", "'{name}' in {file} muss eine Tabelle sein."),
+    ("in_alias", "{error} (alias '{name}' in {file})", "{error} (Alias '{name}' in {file})"),
+    ("alias_not_string", "The expansion must be a string.", "Die Ersetzung muss ein Text sein."),
+    ("alias_empty", "The expansion cannot be empty.", "Die Ersetzung darf nicht leer sein."),
+    (
+        "tag_weight_not_number",
+        "Urgency weight for tag '{tag}' in {file} must be a number.",
+        "Dringlichkeitsgewicht für Schlagwort '{tag}' in {file} muss eine Zahl sein.",
+    ),
+    ("in_report", "{error} (report '{name}' in {file})", "{error} (Bericht '{name}' in {file})"),
+    ("report_not_table", "A report must be a table.", "Ein Bericht muss eine Tabelle sein."),
+    ("unfinished_escape", "Unfinished escape at end of line.", "Unvollständiges Escape am Zeilenende."),
+    ("missing_quote", "Missing closing {quote}.", "Schließendes {quote} fehlt."),
+    (
+        "invalid_date_pattern",
+        "Invalid date format '{format}'. Use MDY, DMY, ISO, or MM, DD and YYYY separated by '/', '-' or '.', e.g. DD.MM.YYYY.",
+        "Ungültiges Datumsformat '{format}'. Möglich sind MDY, DMY, ISO oder MM, DD und YYYY getrennt durch '/', '-' oder '.', z. B. DD.MM.YYYY.",
+    ),
+    (
+        "invalid_when",
+        "Invalid date '{input}'. Use an offset such as +2d, -1w or +1m, today, tomorrow, a weekday or a date.",
+        "Ungültiges Datum '{input}'. Möglich sind ein Versatz wie +2d, -1w oder +1m, today, tomorrow, ein Wochentag oder ein Datum.",
+    ),
+    (
+        "unknown_format",
+        "Unknown format '{format}'. Expected json, ndjson, tsv, table or compact.",
+        "Unbekanntes Format '{format}'. Erwartet: json, ndjson, tsv, table oder compact.",
+    ),
+    (
+        "unknown_color_mode",
+        "Unknown color mode '{mode}'. Expected auto, always or never.",
+        "Unbekannter Farbmodus '{mode}'. Erwartet: auto, always oder never.",
+    ),
+    (
+        "unknown_theme",
+        "Unknown theme '{theme}'. Expected default, mono or pastel.",
+        "Unbekanntes Farbschema '{theme}'. Erwartet: default, mono oder pastel.",
+    ),
+    (
+        "invalid_color_code",
+        "Invalid color code '{code}' for '{key}'.",
+        "Ungültiger Farbcode '{code}' für '{key}'.",
+    ),
+    ("unknown_theme_element", "Unknown theme element '{element}'.", "Unbekanntes Schema-Element '{element}'."),
+    (
+        "unknown_column",
+        "Unknown column '{column}'. Expected id, description, due, priority, status, tags, notes, created, postponed or urgency.",
+        "Unbekannte Spalte '{column}'. Erwartet: id, description, due, priority, status, tags, notes, created, postponed oder urgency.",
+    ),
+    (
+        "unknown_grouping",
+        "Unknown grouping '{group}'. Expected due, priority, tag or status.",
+        "Unbekannte Gruppierung '{group}'. Erwartet: due, priority, tag oder status.",
+    ),
+    (
+        "unknown_report_field",
+        "Unknown report field '{field}'. Expected filter, sort, columns or group.",
+        "Unbekanntes Berichtsfeld '{field}'. Erwartet: filter, sort, columns oder group.",
+    ),
+    ("report_untagged", "Untagged", "Ohne Schlagwort"),
+    (
+        "editor_help",
+        "# Lines starting with '#' above the --- line are ignored.\n# Leave a field empty to clear it. Notes go below the --- line.\n# Delete everything to cancel.",
+        "# Zeilen mit '#' vor der Zeile --- werden ignoriert.\n# Ein leeres Feld wird gelöscht. Notizen stehen unter der Zeile ---.\n# Alles löschen bricht ab.",
+    ),
+    (
+        "editor_expected_field",
+        "expected 'field: value', found '{line}'",
+        "erwartet 'feld: wert', gefunden '{line}'",
+    ),
+    ("editor_duplicate_field", "{field}: given more than once", "{field}: mehr als einmal angegeben"),
+    (
+        "editor_notes_field",
+        "notes: notes go below the {separator} line",
+        "notes: Notizen stehen unter der Zeile {separator}",
+    ),
+    (
+        "editor_unknown_field",
+        "{field}: unknown field, expected description, due, priority or tags",
+        "{field}: unbekanntes Feld, erwartet: description, due, priority oder tags",
+    ),
+    (
+        "editor_missing_separator",
+        "missing the {separator} line that separates the fields from the notes",
+        "die Zeile {separator}, die Felder und Notizen trennt, fehlt",
+    ),
+    (
+        "editor_not_started",
+        "Could not start editor '{command}': {error}",
+        "Editor '{command}' konnte nicht gestartet werden: {error}",
+    ),
+    ("editor_failed", "Editor '{command}' exited with {status}.", "Editor '{command}' endete mit {status}."),
+    (
+        "unknown_history_action",
+        "Unknown history action '{action}'",
+        "Unbekannte Verlaufsaktion '{action}'",
+    ),
+    ("invalid_history_entry", "Invalid history entry '{entry}'", "Ungültiger Verlaufseintrag '{entry}'"),
+];
+
+// Unknown keys come back unchanged, which the tests below guard against.
+pub fn message(language: Language, key: &str) -> &str {
+    MESSAGES
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, en, de)| match language {
+            Language::En => *en,
+            Language::De => *de,
+        })
+        .unwrap_or(key)
+}
+
+pub fn t(key: &str) -> &str {
+    message(language(), key)
+}
+
+pub fn tf(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut text = t(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    text
+}
+
+// The file errors many modules report, with the path and the OS error.
+pub fn read_error(path: &Path, error: impl fmt::Display) -> String {
+    tf(
+        "error_reading_file",
+        &[("file", &path.display()), ("error", &error)],
+    )
+}
+
+pub fn write_error(path: &Path, error: impl fmt::Display) -> String {
+    tf(
+        "error_writing_file",
+        &[("file", &path.display()), ("error", &error)],
+    )
+}
+
+pub fn create_error(path: &Path, error: impl fmt::Display) -> String {
+    tf(
+        "error_creating_dir",
+        &[("dir", &path.display()), ("error", &error)],
+    )
+}
+
+pub fn remove_error(path: &Path, error: impl fmt::Display) -> String {
+    tf(
+        "error_removing_file",
+        &[("file", &path.display()), ("error", &error)],
+    )
+}
+
+pub fn is_yes(answer: &str) -> bool {
+    let answer = answer.trim().to_lowercase();
+    t("yes_answers").split(',').any(|yes| yes == answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    // The string literals of a Rust source file with their line numbers.
+    // Comments and character literals are skipped.
+    fn string_literals(source: &str) -> Vec<(usize, String)> {
+        let chars: Vec<char> = source.chars().collect();
+        let mut literals = Vec::new();
+        let mut line = 1;
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '\n' => line += 1,
+                '/' if chars.get(i + 1) == Some(&'/') => {
+                    while i < chars.len() && chars[i] != '\n' {
+                        i += 1;
+                    }
+                    continue;
+                }
+                '\'' if chars.get(i + 2) == Some(&'\'') => i += 2,
+                '\'' if chars.get(i + 1) == Some(&'\\') => {
+                    i += chars[i + 2..].iter().position(|&c| c == '\'').unwrap_or(0) + 2;
+                }
+                '"' => {
+                    let start = line;
+                    let mut literal = String::new();
+                    i += 1;
+                    while i < chars.len() && chars[i] != '"' {
+                        if chars[i] == '\\' {
+                            literal.push(chars[i]);
+                            i += 1;
+                        }
+                        if chars[i] == '\n' {
+                            line += 1;
+                        }
+                        literal.push(chars[i]);
+                        i += 1;
+                    }
+                    literals.push((start, literal));
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        literals
+    }
+
+    // Prose: outside `{placeholders}`, a capitalized word like every English
+    // message in the catalog starts with, two plain lowercase words, or an
+    // abbreviation such as `n/a`. Command names, error codes, field names,
+    // `label:` lines, environment variables, options passed to other programs
+    // and the `[Section]` and `Key=value` lines of unit files pass.
+    fn is_prose(literal: &str) -> bool {
+        if literal.starts_with("--") {
+            return false;
+        }
+        let mut text = String::new();
+        let mut depth = 0;
+        for c in literal.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ if depth == 0 => text.push(c),
+                _ => {}
+            }
+        }
+        let is_unit_syntax = |word: &str| {
+            (word.starts_with('[') && word.ends_with(']'))
+                || word
+                    .split_once('=')
+                    .is_some_and(|(key, _)| key.chars().all(|c| c.is_ascii_alphanumeric()))
+        };
+        let is_lowercase = |word: &str| {
+            !word.is_empty() && word.chars().all(|c| c.is_alphabetic() && c.is_lowercase())
+        };
+        let text = text.replace("\\n", " ");
+        let words: Vec<&str> = text
+            .split_whitespace()
+            .filter(|word| !is_unit_syntax(word))
+            .collect();
+        let capitalized = words.iter().any(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            chars
+                .windows(2)
+                .any(|pair| pair[0].is_uppercase() && pair[1].is_lowercase())
+        });
+        let lowercase = words
+            .iter()
+            .filter(|word| is_lowercase(word.trim_end_matches(['.', ',', ';', '!', '?'])))
+            .count();
+        let abbreviation = words.iter().any(|word| {
+            word.split_once('/')
+                .is_some_and(|(a, b)| is_lowercase(a) && is_lowercase(b))
+        });
+        capitalized || lowercase >= 2 || abbreviation
+    }
+
+    // Keys passed to `t("...")` and `tf("...")`, but not to e.g. `split("...")`.
+    fn keys_used(source: &str) -> Vec<String> {
+        let mut keys = Vec::new();
+        for call in ["t(\"", "tf(\""] {
+            for (index, _) in source.match_indices(call) {
+                let before = source[..index].chars().next_back().unwrap_or(' ');
+                if before.is_alphanumeric() || before == '_' {
+                    continue;
+                }
+                let rest = &source[index + call.len()..];
+                if let Some((key, _)) = rest.split_once('"') {
+                    keys.push(key.to_string());
+                }
+            }
+        }
+        keys
+    }
+
+    #[test]
+    fn languages_come_from_locale_names() {
+        assert_eq!(Language::from_locale("de_DE.UTF-8"), Language::De);
+        assert_eq!(Language::from_locale("de"), Language::De);
+        assert_eq!(Language::from_locale("en_GB.UTF-8"), Language::En);
+        assert_eq!(Language::from_locale("C"), Language::En);
+        assert_eq!(Language::from_locale("fr_FR"), Language::En);
+    }
+
+    #[test]
+    fn translations_keep_placeholders() {
+        for (key, en, de) in MESSAGES {
+            assert_eq!(
+                placeholders(en),
+                placeholders(de),
+                "placeholders differ in '{key}'"
+            );
+            assert!(!de.is_empty(), "'{key}' has no German text");
+        }
+    }
+
+    #[test]
+    fn lookup_and_fill() {
+        assert_eq!(message(Language::De, "cancelled"), "Abgebrochen.");
+        assert_eq!(message(Language::En, "cancelled"), "Cancelled.");
+        assert_eq!(
+            tf("task_not_found", &[("id", &"3D")]),
+            "Task ID 3D not found."
+        );
+        assert!(is_yes("Yes"));
+        assert!(!is_yes("no"));
+    }
+
+    // Every module that prints messages or returns them as errors.
+    const SOURCES: &[(&str, &str)] = &[
+        ("agenda.rs", include_str!("agenda.rs")),
+        ("bulk.rs", include_str!("bulk.rs")),
+        ("calendar.rs", include_str!("calendar.rs")),
+        ("cli.rs", include_str!("cli.rs")),
+        ("config.rs", include_str!("config.rs")),
+        ("dates.rs", include_str!("dates.rs")),
+        ("editor.rs", include_str!("editor.rs")),
+        ("filter.rs", include_str!("filter.rs")),
+        ("hooks.rs", include_str!("hooks.rs")),
+        ("main.rs", include_str!("main.rs")),
+        ("output.rs", include_str!("output.rs")),
+        ("picker.rs", include_str!("picker.rs")),
+        ("plugin.rs", include_str!("plugin.rs")),
+        ("rank.rs", include_str!("rank.rs")),
+        ("reminders.rs", include_str!("reminders.rs")),
+        ("render.rs", include_str!("render.rs")),
+        ("report.rs", include_str!("report.rs")),
+        ("schedule.rs", include_str!("schedule.rs")),
+        ("shell.rs", include_str!("shell.rs")),
+        ("snooze.rs", include_str!("snooze.rs")),
+        ("stats.rs", include_str!("stats.rs")),
+        ("task_manager.rs", include_str!("task_manager.rs")),
+        ("templates.rs", include_str!("templates.rs")),
+        ("tui.rs", include_str!("tui.rs")),
+        ("urgency.rs", include_str!("urgency.rs")),
+    ];
+
+    #[test]
+    fn every_used_key_is_in_the_catalog() {
+        // Storage formats, whose field and element names are not messages.
+        let others = [
+            ("models.rs", include_str!("models.rs")),
+            ("xml_parser.rs", include_str!("xml_parser.rs")),
+        ];
+        for (_, source) in SOURCES.iter().chain(&others) {
+            for key in keys_used(source) {
+                assert!(
+                    MESSAGES.iter().any(|(k, _, _)| *k == key),
+                    "message '{key}' is missing from the catalog"
+                );
+            }
+        }
+    }
+
+    // Text that reads like prose but must stay as it is in every language.
+    const NOT_MESSAGES: &[&str] = &[
+        // Matched against what `crontab -l` prints.
+        "no crontab",
+        // Found again by later exports.
+        "# todo_cli reminders for {}",
+        "# end of todo_cli reminders for {}",
+        // Command lines.
+        "{} cd {} && {} notify {}",
+        "  systemctl --user enable --now {}",
+        // Only seen if the code itself is wrong.
+        "month validated by parse_month",
+    ];
+
+    #[test]
+    fn commands_have_no_hardcoded_messages() {
+        let hardcoded: Vec<String> = SOURCES
+            .iter()
+            .flat_map(|(file, source)| {
+                // Test fixtures are not messages.
+                let end = ["#[cfg(test)]", "#[cfg(all(test"]
+                    .iter()
+                    .filter_map(|marker| source.find(marker))
+                    .min()
+                    .unwrap_or(source.len());
+                let code = &source[..end];
+                string_literals(code)
+                    .into_iter()
+                    .filter(|(_, literal)| {
+                        is_prose(literal) && !NOT_MESSAGES.contains(&literal.as_str())
+                    })
+                    .map(move |(line, literal)| format!("{file}:{line}: {literal:?}"))
+            })
+            .collect();
+        assert!(
+            hardcoded.is_empty(),
+            "user-facing strings must go through the catalog:\n{}",
+            hardcoded.join("\n")
+        );
+    }
+
+    #[test]
+    fn prose_detection() {
+        assert!(is_prose("No tasks found."));
+        assert!(is_prose("Invalid date '{input}'."));
+        assert!(is_prose("Cancelled."));
+        assert!(!is_prose("invalid_input"));
+        assert!(!is_prose("{}\\t{}"));
+        assert!(!is_prose("== {title} =="));
+        assert!(!is_prose("due_date"));
+        assert!(!is_prose("HOME"));
+        assert!(!is_prose("todo> "));
+        assert!(!is_prose("--object-path=/org/freedesktop/Notifications"));
+        assert!(!is_prose(
+            "[Unit]\\nDescription={description}\\nWantedBy=timers.target"
+        ));
+        assert!(is_prose(
+            "Invalid --set '{assignment}'. Expected FIELD=VALUE."
+        ));
+        assert!(is_prose("n/a"));
+        assert!(is_prose("no tasks found"));
+        assert!(is_prose("{count} tasks left."));
+        assert!(!is_prose("tasks.id_strategy"));
+        assert!(!is_prose("description: {}\\ndue: {}"));
+        assert!(!is_prose("notify-send"));
+    }
+}
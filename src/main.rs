@@ -1,64 +1,346 @@
+mod agenda;
+mod bulk;
+mod calendar;
+mod cli;
+mod config;
+mod dates;
+mod editor;
+mod filter;
+mod hooks;
+mod i18n;
 mod models;
+mod output;
+mod picker;
+mod plugin;
+mod rank;
+mod reminders;
+mod render;
+mod report;
+mod schedule;
+mod shell;
+mod snooze;
+mod stats;
 mod task_manager;
+mod templates;
+mod tui;
+mod urgency;
 mod xml_parser;
 
-use crate::models::{Priority, Task};
+use crate::bulk::FieldUpdate;
+use crate::cli::Args;
+use crate::config::Config;
+use crate::filter::{sort_tasks, Filter, SortKey};
+use crate::i18n::{t, tf};
+use crate::models::{Action, Priority, Task};
+use crate::output::{escape_tsv, print_error, Output, OutputFormat};
+use crate::rank::Place;
+use crate::render::{ColorChoice, Renderer, Theme};
+use crate::report::Report;
+use crate::snooze::DueChange;
+use chrono::Datelike;
 use std::io::stdin;
+use std::path::{Path, PathBuf};
 use std::process;
 use task_manager::{generate_unique_id, TaskManager};
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    let filename = "task_database.xml";
+    let raw_args: Vec<String> = std::env::args().skip(1).collect();
+    let args = Args::parse(&raw_args);
 
-    let Some(command) = args.get(1) else {
-        eprintln!("Usage: todo_cli <new|view|done|edit|help>");
-        process::exit(1);
+    let config = match Config::load() {
+        Ok(config) => config,
+        // A broken config file must not lock `config set` out from fixing it.
+        Err(e) if args.command.as_deref() == Some("config") => {
+            eprintln!("{e}");
+            Config::default()
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
     };
+    i18n::set_language(config.language());
+    urgency::set_weights(config.urgency());
+    let args = config.expand_alias(args);
+    let filename = config.database();
 
-    let mut manager = TaskManager::new();
-    let mut ids_were_missing = false;
+    let out = match build_output(&args, &config) {
+        Ok(out) => out,
+        Err((format, e)) => {
+            print_error(format, "usage", &e);
+            process::exit(1);
+        }
+    };
 
-    match xml_parser::read(filename) {
-        Ok(mut tasks) => {
-            ids_were_missing = tasks.iter().any(|t| t.id.is_empty());
-            if let Err(e) = assign_missing_ids(&mut tasks) {
-                eprintln!("Error assigning task IDs: {e}");
-                process::exit(1);
-            }
-            manager.set_tasks(tasks);
+    let Some(command) = args.command.as_deref() else {
+        out.print_error("usage", t("usage"));
+        process::exit(1);
+    };
+
+    if command == "shell" {
+        let session_args: Vec<String> = raw_args.into_iter().filter(|a| a != "shell").collect();
+        if let Err(e) = shell::run(&config, &session_args) {
+            out.print_error(e.code, &e.message);
+            process::exit(1);
         }
-        Err(e) => {
-            if command != "new" {
-                eprintln!("Could not read {filename}: {e}");
-                process::exit(1);
-            }
+        return;
+    }
+
+    if command == "config" {
+        if let Err(e) = manage_config(&args, &config, &out) {
+            out.print_error(e.code, &e.message);
+            process::exit(1);
         }
+        return;
     }
 
-    match command.as_str() {
-        "new" => {
-            if let Err(e) = create_new_task(&mut manager) {
-                eprintln!("Error creating task: {e}");
+    if let Some(plugin) = external_command(command, &config) {
+        match run_plugin(&plugin, &raw_args, &config, &out) {
+            Ok(code) => process::exit(code),
+            Err(e) => {
+                out.print_error(e.code, &e.message);
                 process::exit(1);
             }
         }
-        "view" => view_tasks(manager.fetch_tasks()),
-        "done" => complete_task(&mut manager, args.get(2).map(String::as_str)),
-        "edit" => edit_task(&mut manager, args.get(2).map(String::as_str)),
-        "help" | _ => print_help(),
     }
 
-    let should_save = ids_were_missing || matches!(command.as_str(), "new" | "done" | "edit");
-    if should_save {
+    let mut manager = TaskManager::new();
+    manager.set_id_strategy(config.id_strategy());
+    manager.set_hooks(config.hooks());
+    let needs_save = match load_tasks(&mut manager, filename) {
+        Ok(needs_save) => needs_save,
+        // `new` creates the file if it does not exist yet, and templates are
+        // kept apart from it. A file that exists but cannot be read is never
+        // saved over.
+        Err(_) if is_missing(filename) && (command == "new" || command == "template") => false,
+        Err(e) => {
+            out.print_error(e.code, &e.message);
+            process::exit(1);
+        }
+    };
+
+    let result = execute(command, &args, &mut manager, &out, &config);
+
+    if needs_save || is_mutating(command) {
         if let Err(e) = manager.save_tasks(filename) {
-            eprintln!("Error saving tasks: {e}");
+            out.print_error("io", &tf("error_saving", &[("error", &e)]));
             process::exit(1);
         }
     }
+
+    if let Err(e) = result {
+        out.print_error(e.code, &e.message);
+        process::exit(1);
+    }
+}
+
+// Reads --format, --color and --theme, falling back to the configured display
+// settings. On error the format to report it in is returned alongside the
+// message.
+fn build_output(args: &Args, config: &Config) -> Result<Output, (OutputFormat, String)> {
+    let format = match args.value("format").map(str::parse::<OutputFormat>) {
+        None => OutputFormat::Table,
+        Some(Ok(format)) => format,
+        Some(Err(e)) => return Err((OutputFormat::Table, e)),
+    };
+
+    let color = match args.value("color").map(str::parse::<ColorChoice>) {
+        None => config.color(),
+        Some(Ok(color)) => color,
+        Some(Err(e)) => return Err((format, e)),
+    };
+
+    let theme = match args.value("theme").map(Theme::parse) {
+        None => config.theme(),
+        Some(Ok(theme)) => theme,
+        Some(Err(e)) => return Err((format, e)),
+    };
+
+    // Machine-readable formats are never colored.
+    let use_color = !format.is_machine_readable() && color.resolve_for_stdout();
+    let renderer = Renderer {
+        date_format: config.output_format(),
+        separator_width: config.separator_width(),
+        due_time: config.due_time(),
+        ..Renderer::new(use_color, theme)
+    };
+    Ok(Output::new(format, renderer))
+}
+
+// Returns whether the file needs saving even if the command changes nothing:
+// tasks without IDs get one, due dates in the old `MM/DD/YYYY` storage
+// format are migrated to ISO, and open tasks without a rank get one.
+fn load_tasks(manager: &mut TaskManager, filename: &str) -> Result<bool, CommandError> {
+    let mut tasks = xml_parser::read(filename).map_err(|e| {
+        CommandError::new(
+            "io",
+            tf("error_reading_file", &[("file", &filename), ("error", &e)]),
+        )
+    })?;
+    let mut needs_save = tasks.iter().any(|t| t.id.is_empty());
+    assign_missing_ids(&mut tasks).map_err(|e| {
+        CommandError::new("id_exhausted", tf("error_assigning_ids", &[("error", &e)]))
+    })?;
+    for task in &mut tasks {
+        needs_save |= task.migrate_due_date();
+    }
+    manager.set_tasks(tasks);
+    needs_save |= manager.rank_unranked();
+    Ok(needs_save)
+}
+
+fn is_missing(filename: &str) -> bool {
+    matches!(std::fs::metadata(filename), Err(e) if e.kind() == std::io::ErrorKind::NotFound)
+}
+
+// A `todo_cli-NAME` plugin on PATH, unless NAME is a built-in command or a
+// report.
+fn external_command(command: &str, config: &Config) -> Option<PathBuf> {
+    if cli::COMMANDS.contains(&command) || config.report(command).is_some() {
+        return None;
+    }
+    plugin::find(command)
+}
+
+// Runs a plugin with the words after its name. It learns where things are
+// from environment variables, and gets a dump of every task when the database
+// can be read.
+fn run_plugin(
+    path: &Path,
+    raw_args: &[String],
+    config: &Config,
+    out: &Output,
+) -> Result<i32, CommandError> {
+    let args = cli::words_after_command(raw_args);
+    let settings: serde_json::Map<String, serde_json::Value> = config
+        .entries()
+        .into_iter()
+        .map(|entry| (entry.key.to_string(), entry.value.into()))
+        .collect();
+    let database =
+        std::path::absolute(config.database()).unwrap_or_else(|_| PathBuf::from(config.database()));
+    let mut vars = vec![
+        ("TODO_CLI_DATABASE", database.display().to_string()),
+        (
+            "TODO_CLI_CONFIG",
+            serde_json::Value::Object(settings).to_string(),
+        ),
+    ];
+    if let Ok(exe) = std::env::current_exe() {
+        vars.push(("TODO_CLI", exe.display().to_string()));
+    }
+
+    let mut manager = TaskManager::new();
+    let tasks = load_tasks(&mut manager, config.database()).ok().map(|_| {
+        let all: Vec<Task> = manager
+            .fetch_tasks()
+            .iter()
+            .chain(manager.fetch_archive())
+            .chain(manager.fetch_trash())
+            .cloned()
+            .collect();
+        Output::new(OutputFormat::Json, out.renderer.clone()).format_tasks(&all, true)
+    });
+
+    plugin::run(path, args, vars, tasks.as_deref()).map_err(|e| {
+        CommandError::new(
+            "io",
+            tf(
+                "error_running_plugin",
+                &[("plugin", &path.display()), ("error", &e)],
+            ),
+        )
+    })
+}
+
+fn is_mutating(command: &str) -> bool {
+    matches!(
+        command,
+        "new"
+            | "clone"
+            | "done"
+            | "edit"
+            | "snooze"
+            | "reschedule"
+            | "move"
+            | "delete"
+            | "trash"
+            | "tui"
+    )
+}
+
+fn execute(
+    command: &str,
+    args: &Args,
+    manager: &mut TaskManager,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    match command {
+        "new" if args.flag("editor") => create_task_in_editor(manager, out, config).map_err(|e| {
+            CommandError::new("invalid_input", tf("error_creating", &[("error", &e)]))
+        }),
+        "new" if args.value("template").is_some() => {
+            create_task_from_template(manager, args.value("template").unwrap_or_default(), out)
+        }
+        "new" => create_new_task(manager, out, config).map_err(|e| {
+            CommandError::new("invalid_input", tf("error_creating", &[("error", &e)]))
+        }),
+        "view" => view_tasks(manager.fetch_tasks(), args, out, config),
+        "show" => show_task(manager, args.positional(0), out),
+        "next" => next_task(manager.fetch_tasks(), args, out, config),
+        "explain" => explain_task(manager, args.positional(0), out),
+        "agenda" => {
+            view_agenda(manager.fetch_tasks(), out);
+            Ok(())
+        }
+        "calendar" => view_calendar(manager.fetch_tasks(), args, out, config),
+        "stats" => view_stats(manager, args, out),
+        "done" if is_bulk(args) => complete_tasks_bulk(manager, args, out, config),
+        "done" => complete_task(manager, args.positional(0), out),
+        "edit" if is_bulk(args) || !args.values("set").is_empty() => {
+            edit_tasks_bulk(manager, args, out, config)
+        }
+        "edit" if args.flag("editor") => {
+            edit_task_in_editor(manager, args.positional(0), out, config)
+        }
+        "edit" => edit_task(manager, args.positional(0), out, config),
+        "snooze" => snooze_tasks(manager, args, out, config),
+        "reschedule" => reschedule_tasks(manager, args, out, config),
+        "delete" => delete_tasks(manager, args, out, config),
+        "trash" => manage_trash(manager, args, out),
+        "template" => manage_templates(manager, args, out),
+        "clone" => clone_task(manager, args, out, config),
+        "move" => move_task(manager, args, out),
+        "remind" => remind(manager, args, out, config),
+        "notify" => notify(manager, args, out, config),
+        "schedule" => schedule(manager, args, config),
+        "tui" => tui::run(manager, &out.renderer, config).map_err(|e| CommandError::new("io", e)),
+        other => match config.report(other) {
+            Some(report) => run_report(report, manager.fetch_tasks(), args, out, config),
+            None => {
+                print_help(config);
+                Ok(())
+            }
+        },
+    }
+}
+
+struct CommandError {
+    code: &'static str,
+    message: String,
+}
+
+impl CommandError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        CommandError {
+            code,
+            message: message.into(),
+        }
+    }
 }
 
-fn assign_missing_ids(tasks: &mut Vec<Task>) -> Result<(), String> {
+fn assign_missing_ids(tasks: &mut [Task]) -> Result<(), String> {
     let mut used_ids: std::collections::HashSet<String> = tasks
         .iter()
         .filter(|t| !t.id.is_empty())
@@ -76,168 +358,1008 @@ fn assign_missing_ids(tasks: &mut Vec<Task>) -> Result<(), String> {
     Ok(())
 }
 
-fn parse_due_date(input: &str) -> Result<String, ()> {
-    let normalized = if input.len() == 8 && input.chars().all(|c| c.is_ascii_digit()) {
-        format!("{}/{}/{}", &input[0..2], &input[2..4], &input[4..8])
-    } else {
-        input.replace(['-', '.', ' '], "/")
+fn read_trimmed_line() -> std::io::Result<String> {
+    let mut input = String::new();
+    // A script that runs out of answers would otherwise spin on a retry prompt.
+    if stdin().read_line(&mut input)? == 0 {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(input.trim().to_string())
+}
+
+fn parse_filter(args: &Args, config: &Config) -> Result<Option<Filter>, CommandError> {
+    args.value("filter")
+        .map(|expr| Filter::parse(expr, &config.input_format()))
+        .transpose()
+        .map_err(|e| CommandError::new("invalid_input", e))
+}
+
+// Filter terms can be given as positional words, with --filter, or both.
+fn filter_terms(args: &Args) -> Option<String> {
+    let mut terms = args.positionals.clone();
+    terms.extend(args.value("filter").map(str::to_string));
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+fn parse_sort(args: &Args, default: Option<SortKey>) -> Result<Option<SortKey>, CommandError> {
+    match args.value("sort") {
+        Some("none") => Ok(None),
+        Some(key) => key
+            .parse()
+            .map(Some)
+            .map_err(|e| CommandError::new("invalid_input", e)),
+        None => Ok(default),
+    }
+}
+
+// Filter terms and --sort override the configured view defaults; `--filter ""`
+// shows everything.
+fn view_tasks(
+    tasks: &[Task],
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let expr = filter_terms(args).or(config.view_filter().map(str::to_string));
+    let filter = match expr {
+        Some(expr) => Some(
+            Filter::parse(&expr, &config.input_format())
+                .map_err(|e| CommandError::new("invalid_input", e))?,
+        ),
+        None => None,
+    };
+    let sort = parse_sort(args, config.view_sort())?;
+
+    let mut selected: Vec<&Task> = match &filter {
+        Some(filter) => filter.apply(tasks, out.renderer.today),
+        None => tasks.iter().collect(),
     };
+    if let Some(sort) = sort {
+        sort_tasks(&mut selected, sort, false);
+    }
+    let tasks: Vec<Task> = selected.into_iter().cloned().collect();
+    if tasks.is_empty() && !out.format.is_machine_readable() {
+        println!("{}", t("no_tasks_found"));
+        return Ok(());
+    }
+    out.print_tasks(&tasks, false);
+    Ok(())
+}
 
-    let parts: Vec<&str> = normalized.split('/').collect();
-    if parts.len() != 3 {
-        return Err(());
+// Extra filter terms on the command line narrow the report's own filter.
+fn run_report(
+    report: &Report,
+    tasks: &[Task],
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let expr = [Some(report.filter.clone()), filter_terms(args)]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let filter = Filter::parse(&expr, &config.input_format())
+        .map_err(|e| CommandError::new("invalid_input", e))?;
+    let mut selected = filter.apply(tasks, out.renderer.today);
+    if let Some(sort) = parse_sort(args, report.sort)? {
+        sort_tasks(&mut selected, sort, false);
     }
 
-    // Zero-pad single-digit month/day; expand 2-digit year to 4-digit
-    fn pad_two(s: &str) -> Result<String, ()> {
-        match s.len() {
-            1 => Ok(format!("0{}", s)),
-            2 => Ok(s.to_string()),
-            _ => Err(()),
+    if selected.is_empty() && !out.format.is_machine_readable() {
+        println!("{}", t("no_tasks_found"));
+        return Ok(());
+    }
+    let groups = match report.group {
+        Some(group) => report::group_tasks(&selected, group, out.renderer.today),
+        None => vec![(String::new(), String::new(), selected)],
+    };
+    // Columns only shape the human-readable formats; the machine-readable
+    // ones keep their stable schema.
+    let output = match out.format {
+        OutputFormat::Table | OutputFormat::Compact => groups
+            .iter()
+            .map(|(_, title, tasks)| {
+                let table = report::render_table(tasks, &report.columns, &out.renderer);
+                if report.group.is_some() {
+                    format!("== {title} ==\n{table}")
+                } else {
+                    table
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        _ if report.group.is_some() => {
+            let groups: Vec<(&str, &str, Vec<&Task>)> = groups
+                .iter()
+                .map(|(key, title, tasks)| (key.as_str(), title.as_str(), tasks.clone()))
+                .collect();
+            out.format_groups(&groups)
         }
+        _ => {
+            let tasks: Vec<Task> = groups[0].2.iter().map(|t| (*t).clone()).collect();
+            out.format_tasks(&tasks, true)
+        }
+    };
+    if !output.is_empty() {
+        println!("{output}");
     }
-    let month_str = pad_two(parts[0])?;
-    let day_str = pad_two(parts[1])?;
-    let year_str = match parts[2].len() {
-        2 => format!("20{}", parts[2]),
-        4 => parts[2].to_string(),
-        _ => return Err(()),
+    Ok(())
+}
+
+fn view_agenda(tasks: &[Task], out: &Output) {
+    if tasks.is_empty() && !out.format.is_machine_readable() {
+        println!("{}", t("no_tasks_found"));
+        return;
+    }
+    let agenda = agenda::build_agenda(tasks, out.renderer.today);
+    let titles: Vec<String> = agenda
+        .iter()
+        .map(|(bucket, _)| bucket.to_string())
+        .collect();
+    let groups: Vec<(&str, &str, Vec<&Task>)> = agenda
+        .iter()
+        .zip(&titles)
+        .map(|((bucket, tasks), title)| (bucket.key(), title.as_str(), tasks.clone()))
+        .collect();
+    let output = out.format_groups(&groups);
+    if !output.is_empty() {
+        println!("{output}");
+    }
+}
+
+fn view_calendar(
+    tasks: &[Task],
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let today = out.renderer.today;
+    let week = args.flag("week");
+
+    let days = if week {
+        let date = match args.positional(0) {
+            Some(input) => config
+                .input_format()
+                .parse_input(input)
+                .ok()
+                .and_then(|date| dates::parse_stored_date(&date))
+                .ok_or_else(|| {
+                    CommandError::new("invalid_input", tf("invalid_date", &[("input", &input)]))
+                })?,
+            None => today,
+        };
+        calendar::week_days(date)
+    } else {
+        let (year, month) = match args.positional(0) {
+            Some(input) => {
+                calendar::parse_month(input).map_err(|e| CommandError::new("invalid_input", e))?
+            }
+            None => (today.year(), today.month()),
+        };
+        if !out.format.is_machine_readable() {
+            let width = calendar::terminal_width();
+            println!(
+                "{}",
+                calendar::render_month(tasks, year, month, width, &out.renderer)
+            );
+            return Ok(());
+        }
+        calendar::month_days(year, month)
     };
 
-    let month: u32 = month_str.parse().map_err(|_| ())?;
-    let day: u32 = day_str.parse().map_err(|_| ())?;
+    if !out.format.is_machine_readable() {
+        let width = calendar::terminal_width();
+        println!(
+            "{}",
+            calendar::render_week(tasks, days[0], width, &out.renderer)
+        );
+        return Ok(());
+    }
 
-    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
-        return Err(());
+    // Machine-readable output is one group per day that has tasks due.
+    let by_day = calendar::tasks_by_day(tasks);
+    let keys: Vec<(String, &Vec<&Task>)> = days
+        .iter()
+        .filter_map(|day| {
+            by_day
+                .get(day)
+                .map(|t| (day.format("%Y-%m-%d").to_string(), t))
+        })
+        .collect();
+    let groups: Vec<(&str, &str, Vec<&Task>)> = keys
+        .iter()
+        .map(|(key, tasks)| (key.as_str(), key.as_str(), (*tasks).clone()))
+        .collect();
+    let output = out.format_groups(&groups);
+    if !output.is_empty() {
+        println!("{output}");
     }
+    Ok(())
+}
+
+fn view_stats(manager: &TaskManager, args: &Args, out: &Output) -> Result<(), CommandError> {
+    let weeks = match args.value("weeks") {
+        Some(n) => n.parse::<usize>().map_err(|_| {
+            CommandError::new("invalid_input", tf("invalid_weeks", &[("input", &n)]))
+        })?,
+        None => 8,
+    };
+    let report = stats::compute(
+        manager.fetch_tasks(),
+        manager.fetch_archive(),
+        out.renderer.today,
+        weeks,
+    );
+    let output = match out.format {
+        OutputFormat::Json => serde_json::to_string_pretty(&report).unwrap_or_default(),
+        OutputFormat::Ndjson => serde_json::to_string(&report).unwrap_or_default(),
+        OutputFormat::Tsv => stats::render_tsv(&report),
+        OutputFormat::Table | OutputFormat::Compact => stats::render_text(&report),
+    };
+    println!("{output}");
+    Ok(())
+}
 
-    Ok(format!("{}/{}/{}", month_str, day_str, year_str))
+// The fuzzy picker replaces the ID listing when a person is at the terminal.
+// Scripts and machine-readable formats keep the plain prompts.
+fn use_picker(out: &Output) -> bool {
+    !out.format.is_machine_readable() && picker::is_available()
 }
 
-fn read_trimmed_line() -> std::io::Result<String> {
-    let mut input = String::new();
-    stdin().read_line(&mut input)?;
-    Ok(input.trim().to_string())
+fn print_tasks_with_ids(tasks: &[Task], out: &Output) {
+    out.print_tasks(tasks, true);
 }
 
-fn view_tasks(tasks: &[Task]) {
-    if tasks.is_empty() {
-        println!("No tasks found.");
-        return;
+fn task_not_found(manager: &TaskManager, id: &str, out: &Output) -> Result<(), CommandError> {
+    let message = tf("task_not_found", &[("id", &id)]);
+    if out.format.is_machine_readable() {
+        return Err(CommandError::new("not_found", message));
     }
-    for task in tasks {
-        println!("{}", task);
+    println!("{message}");
+    print_tasks_with_ids(manager.fetch_tasks(), out);
+    Ok(())
+}
+
+// Prints every field a task has. Projects, dependencies, checklists and
+// recurrence are not part of the task model, so there is nothing to show for
+// them.
+fn show_task(
+    manager: &TaskManager,
+    id_arg: Option<&str>,
+    out: &Output,
+) -> Result<(), CommandError> {
+    let id = match id_arg {
+        Some(id) => id.to_uppercase(),
+        None if !manager.fetch_tasks().is_empty() && use_picker(out) => {
+            match picker::pick(manager.fetch_tasks(), t("picker_show"))
+                .map_err(|e| CommandError::new("io", e))?
+            {
+                Some(id) => id,
+                None => return Ok(()),
+            }
+        }
+        None => return Err(CommandError::new("usage", t("usage_show"))),
+    };
+    match manager.find_any_task_by_id(&id) {
+        Some(task) => {
+            println!("{}", out.format_details(task));
+            Ok(())
+        }
+        None => task_not_found(manager, &id, out),
     }
 }
 
-fn print_tasks_with_ids(tasks: &[Task]) {
-    for task in tasks {
-        println!("ID: {}", task.id);
-        println!("{}", task);
+// The most urgent open task, optionally among those matching filter terms.
+fn next_task(
+    tasks: &[Task],
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let today = out.renderer.today;
+    let selected: Vec<&Task> = match filter_terms(args) {
+        Some(expr) => Filter::parse(&expr, &config.input_format())
+            .map_err(|e| CommandError::new("invalid_input", e))?
+            .apply(tasks, today),
+        None => tasks.iter().collect(),
+    };
+    let Some(task) = urgency::most_urgent(&selected, today) else {
+        if !out.format.is_machine_readable() {
+            println!("{}", t("no_tasks_found"));
+        }
+        return Ok(());
+    };
+    out.print_tasks(std::slice::from_ref(task), true);
+    if !out.format.is_machine_readable() {
+        let score = format!("{:.2}", urgency::score(task, today));
+        println!("{}", tf("urgency_score", &[("score", &score)]));
     }
+    Ok(())
 }
 
-fn create_new_task(manager: &mut TaskManager) -> Result<(), String> {
-    println!("Enter task description: ");
+fn explain_task(
+    manager: &TaskManager,
+    id_arg: Option<&str>,
+    out: &Output,
+) -> Result<(), CommandError> {
+    let Some(id) = id_arg.map(str::to_uppercase) else {
+        return Err(CommandError::new("usage", t("usage_explain")));
+    };
+    let Some(task) = manager.find_any_task_by_id(&id) else {
+        return task_not_found(manager, &id, out);
+    };
+    let weights = urgency::weights();
+    let today = out.renderer.today;
+    let explanation = urgency::Explanation {
+        id: &task.id,
+        urgency: weights.score(task, today),
+        terms: weights.terms(task, today),
+    };
+    let output = match out.format {
+        OutputFormat::Json => serde_json::to_string_pretty(&explanation).unwrap_or_default(),
+        OutputFormat::Ndjson => serde_json::to_string(&explanation).unwrap_or_default(),
+        OutputFormat::Tsv => explanation
+            .terms
+            .iter()
+            .map(|term| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    term.term, term.detail, term.factor, term.weight, term.points
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputFormat::Table | OutputFormat::Compact => {
+            let score = format!("{:.2}", explanation.urgency);
+            format!(
+                "{}\n{}",
+                tf("urgency_of", &[("id", &task.id), ("score", &score)]),
+                urgency::render_terms(&explanation.terms)
+            )
+        }
+    };
+    println!("{output}");
+    Ok(())
+}
+
+fn create_new_task(manager: &mut TaskManager, out: &Output, config: &Config) -> Result<(), String> {
+    eprintln!("{}", t("prompt_description"));
     let description = read_trimmed_line().map_err(|e| e.to_string())?;
 
+    let dates = config.input_format();
     let due_date = loop {
-        println!("Enter task due date (MM/DD/YYYY): ");
+        eprintln!("{}", tf("prompt_due_date", &[("format", &dates)]));
         let input = read_trimmed_line().map_err(|e| e.to_string())?;
-        match parse_due_date(&input) {
+        match dates.parse_input(&input) {
             Ok(date) => break date,
-            Err(()) => println!("Invalid date. Please use MM/DD/YYYY format."),
+            Err(()) => eprintln!("{}", tf("invalid_date_format", &[("format", &dates)])),
         }
     };
 
-    println!("Select a priority:");
-    println!("1. ASAP");
-    println!("2. Important");
-    println!("3. Medium");
-    println!("4. Minor");
-    println!("5. None");
+    eprintln!("{}", t("prompt_priority"));
+    for n in 1..=5 {
+        eprintln!("{n}. {}", Priority::from_menu_number(n));
+    }
 
     let priority = loop {
         let input = read_trimmed_line().map_err(|e| e.to_string())?;
         if input.is_empty() {
-            break Priority::None;
+            break config.default_priority();
         }
         match input.parse::<u8>() {
             Ok(n @ 1..=5) => break Priority::from_menu_number(n),
-            _ => println!("Please enter a number 1-5 or press Enter for None."),
+            _ => eprintln!(
+                "{}",
+                tf(
+                    "priority_retry_default",
+                    &[("priority", &config.default_priority())]
+                )
+            ),
         }
     };
 
-    println!("Enter notes (optional): ");
+    eprintln!("{}", t("prompt_notes"));
     let notes = read_trimmed_line().map_err(|e| e.to_string())?;
 
-    let existing_ids: Vec<&str> = manager.fetch_tasks().iter().map(|t| t.id.as_str()).collect();
-    let id = generate_unique_id(&existing_ids)?;
+    eprintln!("{}", t("prompt_tags"));
+    let tags = Task::parse_tags(&read_trimmed_line().map_err(|e| e.to_string())?);
 
-    manager.add_task(Task {
-        id,
-        description,
-        due_date,
-        priority,
-        notes,
-    });
+    add_new_task(
+        manager,
+        Task {
+            description,
+            due_date,
+            priority,
+            notes,
+            tags,
+            ..Default::default()
+        },
+        out,
+    )
+}
+
+fn create_task_in_editor(
+    manager: &mut TaskManager,
+    out: &Output,
+    config: &Config,
+) -> Result<(), String> {
+    let template = Task {
+        priority: config.default_priority(),
+        ..Default::default()
+    };
+    let Some(updates) = editor::edit_task(&template, &config.input_format())? else {
+        println!("{}", t("cancelled"));
+        return Ok(());
+    };
+    let mut task = template;
+    for update in &updates {
+        update.apply(&mut task);
+    }
+    if task.description.is_empty() {
+        return Err(t("description_empty").to_string());
+    }
+    add_new_task(manager, task, out)
+}
+
+// Asks only for the template's placeholders; everything else comes from the
+// template.
+fn create_task_from_template(
+    manager: &mut TaskManager,
+    name: &str,
+    out: &Output,
+) -> Result<(), CommandError> {
+    let template = templates::load(&templates_dir()?, name)
+        .map_err(|e| CommandError::new("invalid_input", e))?;
+    let mut values = Vec::new();
+    for placeholder in template.placeholders().unwrap_or_default() {
+        eprintln!("{}", tf("prompt_placeholder", &[("name", &placeholder)]));
+        values.push((placeholder, read_trimmed_line().map_err(read_error)?));
+    }
+    let task = template.instantiate(&values, out.renderer.today);
+    add_new_task(manager, task, out)
+        .map_err(|e| CommandError::new("invalid_input", tf("error_creating", &[("error", &e)])))
+}
+
+fn templates_dir() -> Result<PathBuf, CommandError> {
+    config::templates_dir().ok_or_else(|| CommandError::new("io", t("no_templates_dir")))
+}
+
+fn manage_templates(manager: &TaskManager, args: &Args, out: &Output) -> Result<(), CommandError> {
+    let dir = templates_dir()?;
+    let invalid = |e: String| CommandError::new("invalid_input", e);
+    match args.positional(0) {
+        Some("list") | None => {
+            let list = templates::list(&dir).map_err(invalid)?;
+            print_templates(&list, out);
+            Ok(())
+        }
+        Some("save") => {
+            let (Some(name), Some(id)) = (args.positional(1), args.positional(2)) else {
+                return Err(CommandError::new("usage", t("usage_template_save")));
+            };
+            let id = id.to_uppercase();
+            let Some(task) = manager.find_any_task_by_id(&id) else {
+                return Err(CommandError::new(
+                    "not_found",
+                    tf("task_not_found", &[("id", &id)]),
+                ));
+            };
+            let mut template =
+                templates::Template::from_task(name, task, out.renderer.today).map_err(invalid)?;
+            if let Some(due) = args.value("due") {
+                template.set_due(due).map_err(invalid)?;
+            }
+            let path = templates::save(&dir, &template, args.flag("replace")).map_err(invalid)?;
+            if !out.format.is_machine_readable() {
+                println!(
+                    "{}",
+                    tf(
+                        "template_saved",
+                        &[("name", &name), ("path", &path.display())]
+                    )
+                );
+            }
+            Ok(())
+        }
+        Some("delete") => {
+            let Some(name) = args.positional(1) else {
+                return Err(CommandError::new("usage", t("usage_template_delete")));
+            };
+            templates::delete(&dir, name).map_err(invalid)?;
+            if !out.format.is_machine_readable() {
+                println!("{}", tf("template_deleted", &[("name", &name)]));
+            }
+            Ok(())
+        }
+        Some(other) => Err(CommandError::new(
+            "usage",
+            tf("unknown_template_command", &[("command", &other)]),
+        )),
+    }
+}
 
+fn print_templates(list: &[templates::Template], out: &Output) {
+    match out.format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(list).unwrap_or_default())
+        }
+        OutputFormat::Ndjson => {
+            for template in list {
+                println!("{}", serde_json::to_string(template).unwrap_or_default());
+            }
+        }
+        OutputFormat::Tsv => {
+            for template in list {
+                let fields = [
+                    template.name.as_str(),
+                    &template.description,
+                    &template.priority,
+                    &template.due,
+                    &template.tags.join(","),
+                    &template.notes,
+                ];
+                let fields: Vec<String> = fields.iter().map(|f| escape_tsv(f)).collect();
+                println!("{}", fields.join("\t"));
+            }
+        }
+        OutputFormat::Table | OutputFormat::Compact if list.is_empty() => {
+            println!("{}", t("no_templates"))
+        }
+        OutputFormat::Table | OutputFormat::Compact => {
+            let width = list.iter().map(|t| t.name.len()).max().unwrap_or(0);
+            for template in list {
+                println!("{:<width$}  {}", template.name, template.description);
+            }
+        }
+    }
+}
+
+// Copies a task's fields to a new task with a fresh ID. `--due` takes a date
+// or an offset from the original due date, and `--set` overrides fields the
+// way `edit --set` does. Completed and deleted tasks can be cloned too.
+// Checklists, dependencies and recurrence are not part of the task model, so
+// there is nothing more to copy.
+fn clone_task(
+    manager: &mut TaskManager,
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let Some(id) = args.positional(0).map(str::to_uppercase) else {
+        return Err(CommandError::new("usage", t("usage_clone")));
+    };
+    let Some(original) = manager.find_any_task_by_id(&id) else {
+        return task_not_found(manager, &id, out);
+    };
+    let invalid = |e: String| CommandError::new("invalid_input", e);
+    let dates = config.input_format();
+    let today = out.renderer.today;
+
+    let mut task = Task {
+        description: original.description.clone(),
+        due_date: original.due_date.clone(),
+        priority: original.priority.clone(),
+        notes: original.notes.clone(),
+        tags: original.tags.clone(),
+        ..Default::default()
+    };
+    if let Some(due) = args.value("due") {
+        let change = DueChange::parse(due, &dates, today).map_err(invalid)?;
+        task.due_date = change
+            .apply(task.due().unwrap_or(today))
+            .map(dates::format_stored_date)
+            .unwrap_or_default();
+    }
+    for assignment in args.values("set") {
+        FieldUpdate::parse(assignment, &dates)
+            .map_err(invalid)?
+            .apply(&mut task);
+    }
+    if task.description.is_empty() {
+        return Err(invalid(t("description_empty").to_string()));
+    }
+
+    let task = manager
+        .create_task(task)
+        .map_err(|e| CommandError::new("invalid_input", tf("error_creating", &[("error", &e)])))?;
+    if out.format.is_machine_readable() {
+        out.print_tasks(std::slice::from_ref(task), true);
+    } else {
+        println!(
+            "{}",
+            tf(
+                "cloned",
+                &[
+                    ("old_id", &id),
+                    ("id", &task.id),
+                    ("description", &task.description)
+                ]
+            )
+        );
+    }
     Ok(())
 }
 
-fn complete_task(manager: &mut TaskManager, id_arg: Option<&str>) {
+// `remind` lists the reminders still to come. `--check` shows the reminders
+// that are due and exits, for running from cron, and `--daemon` keeps doing
+// that, reloading the database whenever it changes. Fired reminders are noted
+// in a state file next to the database so they never fire twice.
+fn remind(
+    manager: &mut TaskManager,
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    if args.flag("daemon") {
+        return run_reminder_daemon(manager, config);
+    }
+    if args.flag("check") {
+        let mut state = load_reminder_state(config)?;
+        return fire_reminders(manager, &mut state, config);
+    }
+
+    let now = dates::now();
+    let mut upcoming: Vec<(&Task, chrono::NaiveDateTime)> = manager
+        .fetch_tasks()
+        .iter()
+        .filter_map(|task| Some((task, reminders::reminder_time(task, config.due_time())?)))
+        .filter(|(_, at)| *at > now)
+        .collect();
+    upcoming.sort_by_key(|(_, at)| *at);
+    if out.format.is_machine_readable() {
+        let tasks: Vec<Task> = upcoming.iter().map(|(task, _)| (*task).clone()).collect();
+        out.print_tasks(&tasks, true);
+    } else if upcoming.is_empty() {
+        println!("{}", t("no_reminders"));
+    } else {
+        let dates = &out.renderer.date_format;
+        for (task, at) in upcoming {
+            println!(
+                "{}  {} {}  {}",
+                task.id,
+                dates.format(at.date()),
+                at.format("%H:%M"),
+                task.description
+            );
+        }
+    }
+    Ok(())
+}
+
+fn load_reminder_state(config: &Config) -> Result<reminders::State, CommandError> {
+    reminders::State::load(&reminders::state_path(config.database()))
+        .map_err(|e| CommandError::new("io", e))
+}
+
+// Shows every due reminder that has not fired yet. A reminder whose
+// notification fails is tried again next time.
+fn fire_reminders(
+    manager: &TaskManager,
+    state: &mut reminders::State,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let mut failure = None;
+    for (task, at) in reminders::due(
+        manager.fetch_tasks(),
+        state,
+        dates::now(),
+        config.due_time(),
+    ) {
+        match notify_task(task, config) {
+            Ok(()) => state.mark_fired(task, at),
+            Err(e) => failure = Some(e),
+        }
+    }
+    let all: Vec<Task> = manager
+        .fetch_tasks()
+        .iter()
+        .chain(manager.fetch_archive())
+        .chain(manager.fetch_trash())
+        .cloned()
+        .collect();
+    state.prune(&all);
+    state.save().map_err(|e| CommandError::new("io", e))?;
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+fn notify_task(task: &Task, config: &Config) -> Result<(), CommandError> {
+    let text = match task.due() {
+        Some(due) => tf(
+            "reminder_due",
+            &[
+                ("id", &task.id),
+                ("due", &config.output_format().format(due)),
+            ],
+        ),
+        None => tf("reminder_undated", &[("id", &task.id)]),
+    };
+    reminders::notify(config.reminder_command(), &task.description, &text).map_err(|e| {
+        CommandError::new(
+            "io",
+            tf("error_notifying", &[("id", &task.id), ("error", &e)]),
+        )
+    })
+}
+
+// Wakes up for the next reminder, or every `reminders.interval` seconds to
+// look for changes to the database, whichever comes first.
+fn run_reminder_daemon(manager: &mut TaskManager, config: &Config) -> Result<(), CommandError> {
+    let filename = config.database();
+    let modified = || std::fs::metadata(filename).and_then(|m| m.modified()).ok();
+    let mut loaded_at = modified();
+    let mut state = load_reminder_state(config)?;
+    loop {
+        if modified() != loaded_at {
+            // A file caught halfway through being written is read next time.
+            let mut reloaded = TaskManager::new();
+            match load_tasks(&mut reloaded, filename) {
+                Ok(_) => {
+                    *manager = reloaded;
+                    loaded_at = modified();
+                }
+                Err(e) => eprintln!("{}", e.message),
+            }
+        }
+        if let Err(e) = fire_reminders(manager, &mut state, config) {
+            eprintln!("{}", e.message);
+        }
+
+        let now = dates::now();
+        let mut wait = config.reminder_interval();
+        if let Some(next) = reminders::next(manager.fetch_tasks(), now, config.due_time()) {
+            let until = (next - now).to_std().unwrap_or_default();
+            wait = wait.min(until + std::time::Duration::from_secs(1));
+        }
+        std::thread::sleep(wait);
+    }
+}
+
+// Shows an open task's reminder now, unless it already fired. This is what
+// the entries from `schedule export` run; for a task without a reminder the
+// time it is due counts as its reminder.
+fn notify(
+    manager: &TaskManager,
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let Some(id) = args.positional(0).map(str::to_uppercase) else {
+        return Err(CommandError::new("usage", t("usage_notify")));
+    };
+    let Some(task) = manager.find_task_by_id(&id) else {
+        return task_not_found(manager, &id, out);
+    };
+    let mut state = load_reminder_state(config)?;
+    let at = reminders::alert_time(task, config.due_time());
+    if at.is_some_and(|at| state.has_fired(task, at)) {
+        return Ok(());
+    }
+    notify_task(task, config)?;
+    if let Some(at) = at {
+        state.mark_fired(task, at);
+        state.save().map_err(|e| CommandError::new("io", e))?;
+    }
+    Ok(())
+}
+
+// `schedule export --cron|--systemd` turns the reminders still to come into
+// crontab lines or systemd timers that run `notify`. Each export replaces
+// what the previous one for this database wrote.
+fn schedule(manager: &TaskManager, args: &Args, config: &Config) -> Result<(), CommandError> {
+    let usage = || CommandError::new("usage", t("usage_schedule"));
+    if args.positional(0) != Some("export") || args.flag("cron") == args.flag("systemd") {
+        return Err(usage());
+    }
+    let io = |e: std::io::Error| CommandError::new("io", e.to_string());
+    let database = std::path::absolute(config.database()).map_err(io)?;
+    let dir = std::env::current_dir().map_err(io)?;
+    let program = std::env::current_exe().map_err(io)?;
+    let failed = |e: String| CommandError::new("io", tf("error_scheduling", &[("error", &e)]));
+
+    let now = dates::now();
+    let mut entries: Vec<schedule::Entry> = manager
+        .fetch_tasks()
+        .iter()
+        .filter_map(|task| {
+            Some(schedule::Entry {
+                id: task.id.clone(),
+                description: task.description.clone(),
+                at: reminders::alert_time(task, config.due_time()).filter(|at| *at > now)?,
+            })
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.at);
+    let count = entries.len();
+
+    if args.flag("cron") {
+        let file = args.value("file").map(Path::new);
+        let block = schedule::cron_block(&entries, &database, &dir, &program);
+        let changed = schedule::export_cron(&block, &database, file).map_err(failed)?;
+        let target = file.map_or_else(|| "crontab".to_string(), |file| file.display().to_string());
+        let key = if changed {
+            "scheduled_cron"
+        } else {
+            "schedule_unchanged"
+        };
+        println!("{}", tf(key, &[("count", &count), ("target", &target)]));
+        return Ok(());
+    }
+
+    let units_dir = match args.value("file") {
+        Some(dir) => PathBuf::from(dir),
+        None => config::systemd_user_dir()
+            .ok_or_else(|| CommandError::new("io", t("no_systemd_dir")))?,
+    };
+    let units = schedule::systemd_units(&entries, &database, &dir, &program);
+    let changes = schedule::sync_units(&units_dir, &database, &units).map_err(failed)?;
+    println!(
+        "{}",
+        tf(
+            "scheduled_systemd",
+            &[
+                ("count", &count),
+                ("written", &changes.written.len()),
+                ("removed", &changes.removed.len()),
+                ("dir", &units_dir.display()),
+            ]
+        )
+    );
+    let timers: Vec<&str> = changes
+        .written
+        .iter()
+        .map(String::as_str)
+        .filter(|name| name.ends_with(".timer"))
+        .collect();
+    if !changes.written.is_empty() || !changes.removed.is_empty() {
+        println!("{}", t("schedule_apply"));
+        println!("  systemctl --user daemon-reload");
+    }
+    if !timers.is_empty() {
+        println!("  systemctl --user enable --now {}", timers.join(" "));
+    }
+    Ok(())
+}
+
+// Puts an open task at the top or bottom of the manual order, or next to
+// another open task. Only the moved task's rank changes.
+fn move_task(manager: &mut TaskManager, args: &Args, out: &Output) -> Result<(), CommandError> {
+    let usage = || CommandError::new("usage", t("usage_move"));
+    let id = args
+        .positional(0)
+        .map(str::to_uppercase)
+        .ok_or_else(usage)?;
+    let mut places = Vec::new();
+    if args.flag("top") {
+        places.push(Place::Top);
+    }
+    if args.flag("bottom") {
+        places.push(Place::Bottom);
+    }
+    if let Some(other) = args.value("before") {
+        places.push(Place::Before(other.to_uppercase()));
+    }
+    if let Some(other) = args.value("after") {
+        places.push(Place::After(other.to_uppercase()));
+    }
+    let [place] = places.as_slice() else {
+        return Err(usage());
+    };
+    if manager.find_task_by_id(&id).is_none() {
+        return task_not_found(manager, &id, out);
+    }
+    if let Place::Before(other) | Place::After(other) = place {
+        if *other == id {
+            return Err(CommandError::new(
+                "invalid_input",
+                tf("move_self", &[("id", &id)]),
+            ));
+        }
+        if manager.find_task_by_id(other).is_none() {
+            return task_not_found(manager, other, out);
+        }
+    }
+
+    let task = manager.move_task(&id, place).map_err(rejected)?;
+    if out.format.is_machine_readable() {
+        out.print_tasks(std::slice::from_ref(task), true);
+    } else {
+        println!(
+            "{}",
+            tf(
+                "moved",
+                &[("id", &task.id), ("description", &task.description)]
+            )
+        );
+    }
+    Ok(())
+}
+
+fn add_new_task(manager: &mut TaskManager, task: Task, out: &Output) -> Result<(), String> {
+    let task = manager.create_task(task)?;
+    if out.format.is_machine_readable() {
+        out.print_tasks(std::slice::from_ref(task), true);
+    }
+    Ok(())
+}
+
+fn complete_task(
+    manager: &mut TaskManager,
+    id_arg: Option<&str>,
+    out: &Output,
+) -> Result<(), CommandError> {
     let id = if let Some(id) = id_arg {
         id.to_uppercase()
     } else {
         let tasks = manager.fetch_tasks();
         if tasks.is_empty() {
-            println!("No tasks to complete.");
-            return;
+            println!("{}", t("no_tasks_to_complete"));
+            return Ok(());
         }
-        print_tasks_with_ids(tasks);
-        println!("Enter the task ID of the finished task: ");
-        match read_trimmed_line() {
-            Ok(s) => s.to_uppercase(),
-            Err(e) => {
-                eprintln!("Error reading input: {e}");
-                return;
+        if use_picker(out) {
+            match picker::pick(tasks, t("picker_complete"))
+                .map_err(|e| CommandError::new("io", e))?
+            {
+                Some(id) => return complete_task(manager, Some(&id), out),
+                None => return Ok(()),
             }
         }
+        out.print_choices(tasks);
+        eprintln!("{}", t("prompt_complete_id"));
+        read_trimmed_line().map_err(read_error)?.to_uppercase()
     };
 
-    if let Some(task) = manager.remove_task_by_id(&id) {
-        println!("Completed: {}", task.description);
-    } else {
-        println!("Task ID {id} not found.");
-        print_tasks_with_ids(manager.fetch_tasks());
+    match manager.complete_task_by_id(&id).map_err(rejected)? {
+        Some(task) if out.format.is_machine_readable() => {
+            out.print_tasks(std::slice::from_ref(task), true);
+            Ok(())
+        }
+        Some(task) => {
+            println!("{}", tf("completed", &[("description", &task.description)]));
+            Ok(())
+        }
+        None => task_not_found(manager, &id, out),
     }
 }
 
-fn edit_task(manager: &mut TaskManager, id_arg: Option<&str>) {
+fn edit_task(
+    manager: &mut TaskManager,
+    id_arg: Option<&str>,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
     let id = match id_arg {
+        None if !manager.fetch_tasks().is_empty() && use_picker(out) => {
+            match picker::pick(manager.fetch_tasks(), t("picker_edit"))
+                .map_err(|e| CommandError::new("io", e))?
+            {
+                Some(id) => id,
+                None => return Ok(()),
+            }
+        }
         None => {
             if manager.fetch_tasks().is_empty() {
-                println!("No tasks to edit.");
+                println!("{}", t("no_tasks_to_edit"));
             } else {
-                print_tasks_with_ids(manager.fetch_tasks());
+                print_tasks_with_ids(manager.fetch_tasks(), out);
             }
-            return;
+            return Ok(());
         }
         Some(id) => id.to_uppercase(),
     };
 
     let current = match manager.find_task_by_id(&id) {
         Some(task) => task.clone(),
-        None => {
-            println!("Task ID {id} not found.");
-            if !manager.fetch_tasks().is_empty() {
-                print_tasks_with_ids(manager.fetch_tasks());
-            }
-            return;
-        }
+        None => return task_not_found(manager, &id, out),
     };
 
-    println!("Description [{}]: ", current.description);
+    eprintln!(
+        "{}",
+        tf("edit_description", &[("value", &current.description)])
+    );
     let input = read_trimmed_line().unwrap_or_default();
     let new_description = if input.is_empty() {
         current.description.clone()
@@ -245,33 +1367,42 @@ fn edit_task(manager: &mut TaskManager, id_arg: Option<&str>) {
         input
     };
 
+    let dates = config.input_format();
     let new_due_date = loop {
-        println!("Due date [{}]: ", current.due_date);
+        eprintln!(
+            "{}",
+            tf(
+                "edit_due_date",
+                &[("value", &dates.display(&current.due_date))]
+            )
+        );
         let input = read_trimmed_line().unwrap_or_default();
         if input.is_empty() {
             break current.due_date.clone();
         }
-        match parse_due_date(&input) {
+        match dates.parse_input(&input) {
             Ok(date) => break date,
-            Err(()) => println!("Invalid date. Please use MM/DD/YYYY format."),
+            Err(()) => eprintln!("{}", tf("invalid_date_format", &[("format", &dates)])),
         }
     };
 
     let new_priority = loop {
-        println!("Priority [{}]:", current.priority);
-        println!("  1. ASAP\n  2. Important\n  3. Medium\n  4. Minor\n  5. None");
-        println!("Enter 1-5 or press Enter to keep current: ");
+        eprintln!("{}", tf("edit_priority", &[("value", &current.priority)]));
+        for n in 1..=5 {
+            eprintln!("  {n}. {}", Priority::from_menu_number(n));
+        }
+        eprintln!("{}", t("edit_priority_prompt"));
         let input = read_trimmed_line().unwrap_or_default();
         if input.is_empty() {
             break current.priority.clone();
         }
         match input.parse::<u8>() {
             Ok(n @ 1..=5) => break Priority::from_menu_number(n),
-            _ => println!("Please enter a number 1-5 or press Enter to keep current."),
+            _ => eprintln!("{}", t("priority_retry_keep")),
         }
     };
 
-    println!("Notes [{}]: ", current.notes);
+    eprintln!("{}", tf("edit_notes", &[("value", &current.notes)]));
     let input = read_trimmed_line().unwrap_or_default();
     let new_notes = if input.is_empty() {
         current.notes.clone()
@@ -279,70 +1410,566 @@ fn edit_task(manager: &mut TaskManager, id_arg: Option<&str>) {
         input
     };
 
-    if let Some(task) = manager.find_task_by_id_mut(&id) {
-        task.description = new_description;
-        task.due_date = new_due_date;
-        task.priority = new_priority;
-        task.notes = new_notes;
+    eprintln!(
+        "{}",
+        tf("edit_tags", &[("value", &current.tags.join(", "))])
+    );
+    let input = read_trimmed_line().unwrap_or_default();
+    let new_tags = if input.is_empty() {
+        current.tags.clone()
+    } else {
+        Task::parse_tags(&input)
+    };
+
+    let mut task = current;
+    let mut changed = Vec::new();
+    if task.description != new_description {
+        changed.push("description");
+    }
+    if task.due_date != new_due_date {
+        changed.push("due_date");
+    }
+    if task.priority != new_priority {
+        changed.push("priority");
+    }
+    if task.notes != new_notes {
+        changed.push("notes");
+    }
+    if task.tags != new_tags {
+        changed.push("tags");
+    }
+
+    task.description = new_description;
+    task.due_date = new_due_date;
+    task.priority = new_priority;
+    task.notes = new_notes;
+    task.tags = new_tags;
+    if !changed.is_empty() {
+        task.record(Action::Edited, changed.join(","));
+    }
+
+    let task = manager.update_task(task).map_err(rejected)?;
+    if out.format.is_machine_readable() {
+        out.print_tasks(std::slice::from_ref(task), true);
+    } else {
+        println!("{}", task_updated(task, out));
+    }
+    Ok(())
+}
+
+fn edit_task_in_editor(
+    manager: &mut TaskManager,
+    id_arg: Option<&str>,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let Some(id) = id_arg.map(str::to_uppercase) else {
+        return Err(CommandError::new("usage", t("usage_edit_editor")));
+    };
+    let Some(current) = manager.find_task_by_id(&id).cloned() else {
+        return task_not_found(manager, &id, out);
+    };
+    let Some(updates) = editor::edit_task(&current, &config.input_format())
+        .map_err(|e| CommandError::new("invalid_input", e))?
+    else {
+        println!("{}", t("cancelled"));
+        return Ok(());
+    };
+
+    let mut task = current;
+    bulk::apply_updates(&mut task, &updates);
+    let task = manager.update_task(task).map_err(rejected)?;
+    if out.format.is_machine_readable() {
+        out.print_tasks(std::slice::from_ref(task), true);
+    } else {
+        println!("{}", task_updated(task, out));
+    }
+    Ok(())
+}
+
+fn is_bulk(args: &Args) -> bool {
+    args.positionals.len() > 1 || args.value("filter").is_some()
+}
+
+fn read_error(e: std::io::Error) -> CommandError {
+    CommandError::new("io", tf("error_reading_input", &[("error", &e)]))
+}
+
+// A hook vetoed the change.
+fn rejected(e: String) -> CommandError {
+    CommandError::new("rejected", e)
+}
+
+fn task_updated(task: &Task, out: &Output) -> String {
+    tf(
+        "task_updated",
+        &[("id", &task.id), ("task", &out.format_task(task, false))],
+    )
+}
 
-        println!("Task[{}] updated:\n{}", task.id, task);
+// Resolves the IDs given on the command line plus any `--filter` matches,
+// then shows a preview and asks for confirmation unless `--yes` was passed.
+// Returns an empty list when there is nothing to do or the user declined.
+// `question` is the catalog key of the confirmation prompt.
+fn confirm_bulk_targets(
+    manager: &TaskManager,
+    args: &Args,
+    out: &Output,
+    config: &Config,
+    question: &str,
+) -> Result<Vec<String>, CommandError> {
+    let filter = parse_filter(args, config)?;
+    confirm_targets(
+        manager,
+        &args.positionals,
+        filter.as_ref(),
+        args,
+        out,
+        question,
+    )
+}
+
+// Like `confirm_bulk_targets`, for commands whose positionals are not all
+// task IDs.
+fn confirm_targets(
+    manager: &TaskManager,
+    ids: &[String],
+    filter: Option<&Filter>,
+    args: &Args,
+    out: &Output,
+    question: &str,
+) -> Result<Vec<String>, CommandError> {
+    let ids = bulk::select_targets(manager.fetch_tasks(), ids, filter, out.renderer.today)
+        .map_err(|e| CommandError::new("not_found", e))?;
+
+    if ids.is_empty() {
+        if !out.format.is_machine_readable() {
+            println!("{}", t("no_matching_tasks"));
+        }
+        return Ok(ids);
+    }
+    if args.flag("yes") {
+        return Ok(ids);
+    }
+
+    let preview: Vec<Task> = ids
+        .iter()
+        .filter_map(|id| manager.find_task_by_id(id).cloned())
+        .collect();
+    out.print_choices(&preview);
+    eprintln!("{}", tf(question, &[("count", &ids.len())]));
+    if i18n::is_yes(&read_trimmed_line().map_err(read_error)?) {
+        Ok(ids)
+    } else {
+        eprintln!("{}", t("cancelled"));
+        Ok(Vec::new())
     }
 }
 
-fn print_help() {
-    println!("New:  Create a new task");
-    println!("Edit: Modify an existing task");
-    println!("Done: Mark a task as complete");
-    println!("View: Display all tasks");
-    println!("Help: Show this help message");
+fn complete_tasks_bulk(
+    manager: &mut TaskManager,
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let ids = confirm_bulk_targets(manager, args, out, config, "confirm_complete")?;
+    let mut completed = Vec::new();
+    for id in &ids {
+        if let Some(task) = manager.complete_task_by_id(id).map_err(rejected)? {
+            completed.push(task.clone());
+        }
+    }
+
+    if out.format.is_machine_readable() {
+        out.print_tasks(&completed, true);
+    } else {
+        for task in &completed {
+            println!("{}", tf("completed", &[("description", &task.description)]));
+        }
+    }
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn edit_tasks_bulk(
+    manager: &mut TaskManager,
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let dates = config.input_format();
+    let updates = args
+        .values("set")
+        .into_iter()
+        .map(|assignment| FieldUpdate::parse(assignment, &dates))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| CommandError::new("invalid_input", e))?;
+    if updates.is_empty() {
+        return Err(CommandError::new("usage", t("bulk_edit_requires_set")));
+    }
 
-    #[test]
-    fn test_parse_due_date_valid() {
-        assert_eq!(parse_due_date("01/06/2026"), Ok("01/06/2026".to_string()));
+    let ids = confirm_bulk_targets(manager, args, out, config, "confirm_update")?;
+    let mut updated = Vec::new();
+    for id in &ids {
+        if let Some(mut task) = manager.find_task_by_id(id).cloned() {
+            bulk::apply_updates(&mut task, &updates);
+            updated.push(manager.update_task(task).map_err(rejected)?.clone());
+        }
     }
 
-    #[test]
-    fn test_parse_due_date_dash_delimited() {
-        assert_eq!(parse_due_date("01-06-2026"), Ok("01/06/2026".to_string()));
+    if out.format.is_machine_readable() {
+        out.print_tasks(&updated, true);
+    } else {
+        for task in &updated {
+            println!("{}", task_updated(task, out));
+        }
     }
+    Ok(())
+}
+
+// `snooze ID... WHEN`: the last word says when, the rest are task IDs.
+// `--overdue` adds every overdue task. A single ID is snoozed without asking.
+fn snooze_tasks(
+    manager: &mut TaskManager,
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let Some((when, ids)) = args.positionals.split_last() else {
+        return Err(CommandError::new("usage", t("usage_snooze")));
+    };
+    let today = out.renderer.today;
+    let change = DueChange::parse(when, &config.input_format(), today)
+        .map_err(|e| CommandError::new("invalid_input", e))?;
 
-    #[test]
-    fn test_parse_due_date_eight_digit() {
-        assert_eq!(parse_due_date("01062026"), Ok("01/06/2026".to_string()));
+    let terms: Vec<&str> = [
+        args.flag("overdue").then_some("due:overdue"),
+        args.value("filter"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let ids = if terms.is_empty() && ids.len() == 1 {
+        let id = ids[0].to_uppercase();
+        if manager.find_task_by_id(&id).is_none() {
+            return task_not_found(manager, &id, out);
+        }
+        vec![id]
+    } else if terms.is_empty() && ids.is_empty() {
+        return Err(CommandError::new("usage", t("usage_snooze")));
+    } else {
+        let filter = (!terms.is_empty())
+            .then(|| Filter::parse(&terms.join(" "), &config.input_format()))
+            .transpose()
+            .map_err(|e| CommandError::new("invalid_input", e))?;
+        confirm_targets(manager, ids, filter.as_ref(), args, out, "confirm_snooze")?
+    };
+    move_due_dates(manager, &ids, change, true, out)
+}
+
+// `reschedule` shifts every due date by the same offset, so a batch keeps its
+// spacing. Tasks without a due date are skipped.
+fn reschedule_tasks(
+    manager: &mut TaskManager,
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let Some(shift) = args.value("shift") else {
+        return Err(CommandError::new("usage", t("usage_reschedule")));
+    };
+    if args.positionals.is_empty() && args.value("filter").is_none() {
+        return Err(CommandError::new("usage", t("usage_reschedule")));
     }
+    let change = DueChange::parse(shift, &config.input_format(), out.renderer.today)
+        .map_err(|e| CommandError::new("invalid_input", e))?;
+    if !change.is_offset() {
+        return Err(CommandError::new(
+            "invalid_input",
+            tf("shift_needs_offset", &[("input", &shift)]),
+        ));
+    }
+    let ids = confirm_bulk_targets(manager, args, out, config, "confirm_reschedule")?;
+    move_due_dates(manager, &ids, change, false, out)
+}
 
-    #[test]
-    fn test_parse_due_date_invalid_month() {
-        assert_eq!(parse_due_date("13/06/2026"), Err(()));
+fn move_due_dates(
+    manager: &mut TaskManager,
+    ids: &[String],
+    change: DueChange,
+    from_today: bool,
+    out: &Output,
+) -> Result<(), CommandError> {
+    let today = out.renderer.today;
+    let mut moved = Vec::new();
+    let mut skipped = 0;
+    for id in ids {
+        if let Some(mut task) = manager.find_task_by_id(id).cloned() {
+            if snooze::move_due_date(&mut task, change, today, from_today) {
+                moved.push(manager.update_task(task).map_err(rejected)?.clone());
+            } else if task.due_date.is_empty() {
+                skipped += 1;
+            }
+        }
     }
 
-    #[test]
-    fn test_parse_due_date_invalid_day() {
-        assert_eq!(parse_due_date("01/32/2026"), Err(()));
+    if out.format.is_machine_readable() {
+        out.print_tasks(&moved, true);
+        return Ok(());
+    }
+    for task in &moved {
+        println!(
+            "{}",
+            tf(
+                "rescheduled",
+                &[
+                    ("due", &out.renderer.format_due(task)),
+                    ("description", &task.description),
+                    ("id", &task.id)
+                ]
+            )
+        );
+    }
+    if skipped > 0 {
+        println!("{}", tf("skipped_undated", &[("count", &skipped)]));
     }
+    Ok(())
+}
+
+fn delete_tasks(
+    manager: &mut TaskManager,
+    args: &Args,
+    out: &Output,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let ids = if is_bulk(args) {
+        confirm_bulk_targets(manager, args, out, config, "confirm_delete")?
+    } else if let Some(id) = args.positional(0) {
+        let id = id.to_uppercase();
+        if manager.find_task_by_id(&id).is_none() {
+            return task_not_found(manager, &id, out);
+        }
+        vec![id]
+    } else {
+        if manager.fetch_tasks().is_empty() {
+            println!("{}", t("no_tasks_to_delete"));
+        } else {
+            print_tasks_with_ids(manager.fetch_tasks(), out);
+        }
+        return Ok(());
+    };
 
-    #[test]
-    fn test_parse_due_date_short_digits() {
-        assert_eq!(parse_due_date("0106202"), Err(()));
+    let mut deleted = Vec::new();
+    for id in &ids {
+        if let Some(task) = manager.delete_task_by_id(id).map_err(rejected)? {
+            deleted.push(task.clone());
+        }
     }
 
-    #[test]
-    fn test_parse_due_date_single_digit_parts() {
-        assert_eq!(parse_due_date("1/1/2026"), Ok("01/01/2026".to_string()));
+    if out.format.is_machine_readable() {
+        out.print_tasks(&deleted, true);
+    } else {
+        for task in &deleted {
+            println!(
+                "{}",
+                tf(
+                    "moved_to_trash",
+                    &[("description", &task.description), ("id", &task.id)]
+                )
+            );
+        }
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_parse_due_date_two_digit_year() {
-        assert_eq!(parse_due_date("01/06/26"), Ok("01/06/2026".to_string()));
+fn manage_trash(manager: &mut TaskManager, args: &Args, out: &Output) -> Result<(), CommandError> {
+    match args.positional(0) {
+        Some("list") | None => {
+            if manager.fetch_trash().is_empty() && !out.format.is_machine_readable() {
+                println!("{}", t("trash_empty"));
+            } else {
+                print_tasks_with_ids(manager.fetch_trash(), out);
+            }
+            Ok(())
+        }
+        Some("restore") => {
+            let ids = &args.positionals[1..];
+            if ids.is_empty() {
+                return Err(CommandError::new("usage", t("usage_trash_restore")));
+            }
+            let mut restored = Vec::new();
+            for id in ids {
+                let id = id.to_uppercase();
+                if !manager.fetch_trash().iter().any(|t| t.id == id) {
+                    return Err(CommandError::new(
+                        "not_found",
+                        tf("not_in_trash", &[("id", &id)]),
+                    ));
+                }
+                let task = manager.restore_task_by_id(&id).map_err(rejected)?;
+                if !out.format.is_machine_readable() {
+                    let key = if task.id == id {
+                        "restored"
+                    } else {
+                        "restored_new_id"
+                    };
+                    println!(
+                        "{}",
+                        tf(
+                            key,
+                            &[
+                                ("description", &task.description),
+                                ("id", &task.id),
+                                ("old_id", &id)
+                            ]
+                        )
+                    );
+                }
+                restored.push(task.clone());
+            }
+            if out.format.is_machine_readable() {
+                out.print_tasks(&restored, true);
+            }
+            Ok(())
+        }
+        Some("empty") => {
+            let cutoff = match args.value("older-than") {
+                Some(age) => {
+                    let cutoff = dates::age_cutoff(age, dates::now()).ok_or_else(|| {
+                        CommandError::new("invalid_input", tf("invalid_age", &[("input", &age)]))
+                    })?;
+                    Some(cutoff)
+                }
+                None => None,
+            };
+            if !args.flag("yes") {
+                eprintln!("{}", t("confirm_empty_trash"));
+                if !i18n::is_yes(&read_trimmed_line().map_err(read_error)?) {
+                    eprintln!("{}", t("cancelled"));
+                    return Ok(());
+                }
+            }
+            let removed = manager.empty_trash(cutoff);
+            if !out.format.is_machine_readable() {
+                println!("{}", tf("trash_emptied", &[("count", &removed)]));
+            }
+            Ok(())
+        }
+        Some(other) => Err(CommandError::new(
+            "usage",
+            tf("unknown_trash_command", &[("command", &other)]),
+        )),
     }
+}
 
-    #[test]
-    fn test_parse_due_date_single_digit_and_two_digit_year() {
-        assert_eq!(parse_due_date("1/1/26"), Ok("01/01/2026".to_string()));
+fn print_entries(entries: &[config::Entry], out: &Output) {
+    match out.format {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(entries).unwrap_or_default()
+            )
+        }
+        OutputFormat::Ndjson => {
+            for entry in entries {
+                println!("{}", serde_json::to_string(entry).unwrap_or_default());
+            }
+        }
+        OutputFormat::Tsv => {
+            for entry in entries {
+                println!("{}\t{}\t{}", entry.key, entry.value, entry.source);
+            }
+        }
+        OutputFormat::Table | OutputFormat::Compact => {
+            let width = entries.iter().map(|e| e.key.len()).max().unwrap_or(0);
+            for entry in entries {
+                println!(
+                    "{:<width$} = {:?}  ({})",
+                    entry.key, entry.value, entry.source
+                );
+            }
+        }
+    }
+}
+
+fn manage_config(args: &Args, config: &Config, out: &Output) -> Result<(), CommandError> {
+    let invalid = |e: String| CommandError::new("invalid_input", e);
+    match args.positional(0) {
+        Some("list") | None => {
+            print_entries(&config.entries(), out);
+            Ok(())
+        }
+        Some("get") => {
+            let Some(key) = args.positional(1) else {
+                return Err(CommandError::new("usage", t("usage_config_get")));
+            };
+            let (value, _) = config.get(key).map_err(invalid)?;
+            if out.format.is_machine_readable() {
+                let entries: Vec<config::Entry> = config
+                    .entries()
+                    .into_iter()
+                    .filter(|e| e.key == key)
+                    .collect();
+                print_entries(&entries, out);
+            } else {
+                println!("{value}");
+            }
+            Ok(())
+        }
+        Some("set") => {
+            let (Some(key), Some(value)) = (args.positional(1), args.positional(2)) else {
+                return Err(CommandError::new("usage", t("usage_config_set")));
+            };
+            let path = if args.flag("local") {
+                config::local_path()
+            } else {
+                config::global_path().ok_or_else(|| invalid(t("no_config_dir").to_string()))?
+            };
+            config::set_value(&path, key, value).map_err(invalid)?;
+            if !out.format.is_machine_readable() {
+                println!(
+                    "{}",
+                    tf(
+                        "config_set",
+                        &[
+                            ("key", &key),
+                            ("value", &format!("{value:?}")),
+                            ("path", &path.display())
+                        ]
+                    )
+                );
+            }
+            Ok(())
+        }
+        Some(other) => Err(CommandError::new(
+            "usage",
+            tf("unknown_config_command", &[("command", &other)]),
+        )),
+    }
+}
+
+fn print_help(config: &Config) {
+    println!("{}", t("help"));
+
+    if !config.aliases().is_empty() {
+        println!();
+        println!("{}", t("help_aliases"));
+        for (name, expansion) in config.aliases() {
+            println!("  {name} = {expansion}");
+        }
+    }
+    if !config.reports().is_empty() {
+        println!();
+        println!("{}", t("help_reports"));
+        for report in config.reports() {
+            println!("  {}: {}", report.name, report.summary());
+        }
+    }
+    let plugins = plugin::list();
+    if !plugins.is_empty() {
+        println!();
+        println!("{}", t("help_plugins"));
+        for name in plugins {
+            println!("  {name}");
+        }
     }
 }
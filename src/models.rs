@@ -1,25 +1,29 @@
+use crate::dates::{
+    format_stored_date, format_timestamp, now, parse_stored_date, parse_timestamp, DatePattern,
+};
+use crate::i18n::{message, t, tf, LANGUAGES};
+use chrono::{NaiveDate, NaiveDateTime};
 use std::convert::Infallible;
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Clone)]
+// Variants are declared from most to least urgent, so the derived `Ord`
+// sorts the most urgent tasks first.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
 pub enum Priority {
     Asap,
     Important,
     Medium,
     Minor,
+    #[default]
     None,
 }
 
+// Shown in the user's language; `name` gives the fixed English name used in
+// the task file and in machine-readable output.
 impl fmt::Display for Priority {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Priority::Asap => write!(f, "ASAP"),
-            Priority::Important => write!(f, "Important"),
-            Priority::Medium => write!(f, "Medium"),
-            Priority::Minor => write!(f, "Minor"),
-            Priority::None => write!(f, "None"),
-        }
+        write!(f, "{}", t(self.message_key()))
     }
 }
 
@@ -27,17 +31,64 @@ impl FromStr for Priority {
     type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Infallible> {
-        Ok(match s.to_lowercase().as_str() {
-            "asap" => Priority::Asap,
-            "important" => Priority::Important,
-            "medium" => Priority::Medium,
-            "minor" => Priority::Minor,
-            _ => Priority::None,
-        })
+        Ok(Priority::from_label(s).unwrap_or(Priority::None))
     }
 }
 
 impl Priority {
+    const ALL: [Priority; 5] = [
+        Priority::Asap,
+        Priority::Important,
+        Priority::Medium,
+        Priority::Minor,
+        Priority::None,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Priority::Asap => "ASAP",
+            Priority::Important => "Important",
+            Priority::Medium => "Medium",
+            Priority::Minor => "Minor",
+            Priority::None => "None",
+        }
+    }
+
+    fn message_key(&self) -> &'static str {
+        match self {
+            Priority::Asap => "priority_asap",
+            Priority::Important => "priority_important",
+            Priority::Medium => "priority_medium",
+            Priority::Minor => "priority_minor",
+            Priority::None => "priority_none",
+        }
+    }
+
+    // Matches the name in any language, whatever the current one is, so files
+    // and commands keep working when the language changes.
+    fn from_label(s: &str) -> Option<Priority> {
+        let s = s.trim().to_lowercase();
+        Priority::ALL.into_iter().find(|p| {
+            p.name().to_lowercase() == s
+                || LANGUAGES
+                    .iter()
+                    .any(|&language| message(language, p.message_key()).to_lowercase() == s)
+        })
+    }
+
+    // Strict counterpart to `from_str`: accepts a priority name or its menu
+    // number, and rejects anything else instead of falling back to `None`.
+    pub fn from_name(s: &str) -> Option<Priority> {
+        match s.trim() {
+            "1" => Some(Priority::Asap),
+            "2" => Some(Priority::Important),
+            "3" => Some(Priority::Medium),
+            "4" => Some(Priority::Minor),
+            "5" => Some(Priority::None),
+            other => Priority::from_label(other),
+        }
+    }
+
     pub fn from_menu_number(n: u8) -> Priority {
         match n {
             1 => Priority::Asap,
@@ -49,19 +100,201 @@ impl Priority {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Status {
+    #[default]
+    Open,
+    Done,
+    Deleted,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Open => write!(f, "Open"),
+            Status::Done => write!(f, "Done"),
+            Status::Deleted => write!(f, "Deleted"),
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        Ok(match s.to_lowercase().as_str() {
+            "done" => Status::Done,
+            "deleted" => Status::Deleted,
+            _ => Status::Open,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    Created,
+    Edited,
+    Completed,
+    Deleted,
+    Restored,
+    Postponed,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Created => write!(f, "created"),
+            Action::Edited => write!(f, "edited"),
+            Action::Completed => write!(f, "completed"),
+            Action::Deleted => write!(f, "deleted"),
+            Action::Restored => write!(f, "restored"),
+            Action::Postponed => write!(f, "postponed"),
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "created" => Ok(Action::Created),
+            "edited" => Ok(Action::Edited),
+            "completed" => Ok(Action::Completed),
+            "deleted" => Ok(Action::Deleted),
+            "restored" => Ok(Action::Restored),
+            "postponed" => Ok(Action::Postponed),
+            other => Err(tf("unknown_history_action", &[("action", &other)])),
+        }
+    }
+}
+
+// One line of a task's operation history, e.g. "edited due_date,priority".
 #[derive(Debug, PartialEq, Clone)]
+pub struct HistoryEntry {
+    pub at: NaiveDateTime,
+    pub action: Action,
+    pub detail: String,
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", format_timestamp(self.at), self.action)?;
+        if !self.detail.is_empty() {
+            write!(f, " {}", self.detail)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for HistoryEntry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut parts = s.trim().splitn(3, ' ');
+        let at = parts
+            .next()
+            .and_then(parse_timestamp)
+            .ok_or_else(|| tf("invalid_history_entry", &[("entry", &s)]))?;
+        let action = parts.next().unwrap_or_default().parse()?;
+        let detail = parts.next().unwrap_or_default().to_string();
+        Ok(HistoryEntry { at, action, detail })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
 pub struct Task {
     pub id: String,
     pub description: String,
     pub due_date: String,
     pub priority: Priority,
     pub notes: String,
+    pub tags: Vec<String>,
+    pub status: Status,
+    pub rank: String,
+    pub remind: String,
+    pub history: Vec<HistoryEntry>,
+}
+
+impl Task {
+    pub fn due(&self) -> Option<NaiveDate> {
+        parse_stored_date(&self.due_date)
+    }
+
+    // Rewrites a due date from the old `MM/DD/YYYY` storage format to ISO.
+    // Returns whether anything changed; unreadable dates are left alone.
+    pub fn migrate_due_date(&mut self) -> bool {
+        match self.due().map(format_stored_date) {
+            Some(iso) if iso != self.due_date => {
+                self.due_date = iso;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn parse_tags(input: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in input.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
+
+    pub fn record(&mut self, action: Action, detail: impl Into<String>) {
+        self.history.push(HistoryEntry {
+            at: now(),
+            action,
+            detail: detail.into(),
+        });
+    }
+
+    fn first_time(&self, action: Action) -> Option<NaiveDateTime> {
+        self.history
+            .iter()
+            .find(|e| e.action == action)
+            .map(|e| e.at)
+    }
+
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.first_time(Action::Created)
+    }
+
+    fn last_time(&self, action: Action) -> Option<NaiveDateTime> {
+        self.history
+            .iter()
+            .rev()
+            .find(|e| e.action == action)
+            .map(|e| e.at)
+    }
+
+    pub fn completed_at(&self) -> Option<NaiveDateTime> {
+        self.last_time(Action::Completed)
+    }
+
+    pub fn deleted_at(&self) -> Option<NaiveDateTime> {
+        self.last_time(Action::Deleted)
+    }
+
+    pub fn modified_at(&self) -> Option<NaiveDateTime> {
+        self.history.last().map(|e| e.at)
+    }
+
+    // How often the due date was moved later by `snooze` or `reschedule`.
+    pub fn postpone_count(&self) -> usize {
+        self.history
+            .iter()
+            .filter(|e| e.action == Action::Postponed)
+            .count()
+    }
 }
 
 impl fmt::Display for Task {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let sep = "─".repeat(40);
-        write!(f, "{}\n", sep)?;
+        writeln!(f, "{}", sep)?;
         if self.priority == Priority::None {
             writeln!(f, "{}", self.description)?;
         } else {
@@ -70,7 +303,7 @@ impl fmt::Display for Task {
         if !self.notes.is_empty() {
             writeln!(f, "\"{}\"", self.notes)?;
         }
-        writeln!(f, "{}", self.due_date)?;
+        writeln!(f, "{}", DatePattern::default().display(&self.due_date))?;
         write!(f, "{}", sep)
     }
 }
@@ -88,6 +321,45 @@ mod tests {
         assert_eq!(format!("{}", Priority::None), "None");
     }
 
+    #[test]
+    fn priority_names_are_accepted_in_any_language() {
+        assert_eq!(Priority::from_name("wichtig"), Some(Priority::Important));
+        assert_eq!(Priority::from_name("Sofort"), Some(Priority::Asap));
+        assert_eq!("keine".parse::<Priority>(), Ok(Priority::None));
+        assert_eq!("Gering".parse::<Priority>(), Ok(Priority::Minor));
+        assert_eq!(Priority::Medium.name(), "Medium");
+    }
+
+    #[test]
+    fn priority_from_name_is_strict() {
+        assert_eq!(Priority::from_name("ASAP"), Some(Priority::Asap));
+        assert_eq!(Priority::from_name("4"), Some(Priority::Minor));
+        assert_eq!(Priority::from_name("none"), Some(Priority::None));
+        assert_eq!(Priority::from_name("urgent"), None);
+    }
+
+    #[test]
+    fn priority_orders_most_urgent_first() {
+        let mut priorities = vec![
+            Priority::None,
+            Priority::Medium,
+            Priority::Asap,
+            Priority::Minor,
+            Priority::Important,
+        ];
+        priorities.sort();
+        assert_eq!(
+            priorities,
+            vec![
+                Priority::Asap,
+                Priority::Important,
+                Priority::Medium,
+                Priority::Minor,
+                Priority::None,
+            ]
+        );
+    }
+
     fn make_task(priority: Priority, notes: &str) -> Task {
         Task {
             id: "1A".to_string(),
@@ -95,6 +367,7 @@ mod tests {
             due_date: "01/06/2026".to_string(),
             priority,
             notes: notes.to_string(),
+            ..Default::default()
         }
     }
 
@@ -118,6 +391,42 @@ mod tests {
         assert!(output.contains("01/06/2026"));
     }
 
+    #[test]
+    fn history_entry_round_trip() {
+        let entry: HistoryEntry = "2026-10-19T09:30:00 edited due_date,priority"
+            .parse()
+            .unwrap();
+        assert_eq!(entry.action, Action::Edited);
+        assert_eq!(entry.detail, "due_date,priority");
+        assert_eq!(
+            entry.to_string(),
+            "2026-10-19T09:30:00 edited due_date,priority"
+        );
+
+        let entry: HistoryEntry = "2026-10-19T09:30:00 created".parse().unwrap();
+        assert_eq!(entry.detail, "");
+        assert_eq!(entry.to_string(), "2026-10-19T09:30:00 created");
+
+        assert!("yesterday created".parse::<HistoryEntry>().is_err());
+        assert!("2026-10-19T09:30:00 exploded"
+            .parse::<HistoryEntry>()
+            .is_err());
+    }
+
+    #[test]
+    fn timestamps_come_from_history() {
+        let mut task = make_task(Priority::None, "");
+        assert_eq!(task.created_at(), None);
+        task.history = vec![
+            "2026-10-01T08:00:00 created".parse().unwrap(),
+            "2026-10-02T08:00:00 edited notes".parse().unwrap(),
+            "2026-10-03T08:00:00 completed".parse().unwrap(),
+        ];
+        assert_eq!(task.created_at(), parse_timestamp("2026-10-01T08:00:00"));
+        assert_eq!(task.completed_at(), parse_timestamp("2026-10-03T08:00:00"));
+        assert_eq!(task.modified_at(), parse_timestamp("2026-10-03T08:00:00"));
+    }
+
     #[test]
     fn task_display_none_priority() {
         let task = make_task(Priority::None, "");
@@ -125,4 +434,17 @@ mod tests {
         assert!(output.contains("Buy groceries\n"));
         assert!(!output.contains("(None)"));
     }
+
+    #[test]
+    fn migrates_legacy_due_dates_to_iso() {
+        let mut task = make_task(Priority::None, "");
+        task.due_date = "1/5/2026".to_string();
+        assert!(task.migrate_due_date());
+        assert_eq!(task.due_date, "2026-01-05");
+        assert!(!task.migrate_due_date());
+
+        task.due_date = "someday".to_string();
+        assert!(!task.migrate_due_date());
+        assert_eq!(task.due_date, "someday");
+    }
 }
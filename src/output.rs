@@ -0,0 +1,422 @@
+use crate::dates::format_timestamp;
+use crate::i18n::tf;
+use crate::models::Task;
+use crate::render::Renderer;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Compact,
+    Json,
+    Ndjson,
+    Tsv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "compact" => Ok(OutputFormat::Compact),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            "tsv" => Ok(OutputFormat::Tsv),
+            other => Err(tf("unknown_format", &[("format", &other)])),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OutputFormat::Table => write!(f, "table"),
+            OutputFormat::Compact => write!(f, "compact"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Ndjson => write!(f, "ndjson"),
+            OutputFormat::Tsv => write!(f, "tsv"),
+        }
+    }
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Ndjson)
+    }
+
+    pub fn is_machine_readable(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Tsv
+        )
+    }
+}
+
+// The stable field schema shared by the json, ndjson and tsv formats. New
+// fields are only ever appended, so consumers can rely on the existing order.
+#[derive(Serialize)]
+pub struct TaskRecord<'a> {
+    pub id: &'a str,
+    pub description: &'a str,
+    pub due_date: &'a str,
+    pub priority: String,
+    pub notes: &'a str,
+    pub status: String,
+    pub created: Option<String>,
+    pub modified: Option<String>,
+    pub completed: Option<String>,
+    pub tags: &'a [String],
+    pub postponed: usize,
+    pub rank: &'a str,
+    pub remind: &'a str,
+}
+
+impl<'a> From<&'a Task> for TaskRecord<'a> {
+    fn from(task: &'a Task) -> Self {
+        TaskRecord {
+            id: &task.id,
+            description: &task.description,
+            due_date: &task.due_date,
+            priority: task.priority.name().to_string(),
+            notes: &task.notes,
+            status: task.status.to_string(),
+            created: task.created_at().map(format_timestamp),
+            modified: task.modified_at().map(format_timestamp),
+            completed: task.completed_at().map(format_timestamp),
+            tags: &task.tags,
+            postponed: task.postpone_count(),
+            rank: &task.rank,
+            remind: &task.remind,
+        }
+    }
+}
+
+// `show` output: the task record followed by its history.
+#[derive(Serialize)]
+struct DetailRecord<'a> {
+    #[serde(flatten)]
+    task: TaskRecord<'a>,
+    history: Vec<HistoryRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct HistoryRecord<'a> {
+    at: String,
+    action: String,
+    detail: &'a str,
+}
+
+#[derive(Serialize)]
+struct GroupRecord<'a> {
+    group: &'a str,
+    tasks: Vec<TaskRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct ErrorRecord<'a> {
+    error: ErrorBody<'a>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    code: &'a str,
+    message: &'a str,
+}
+
+pub fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Output {
+    pub format: OutputFormat,
+    pub renderer: Renderer,
+}
+
+impl Output {
+    pub fn new(format: OutputFormat, renderer: Renderer) -> Self {
+        Output { format, renderer }
+    }
+
+    pub fn format_task(&self, task: &Task, with_id: bool) -> String {
+        match self.format {
+            OutputFormat::Table if with_id => {
+                format!("ID: {}\n{}", task.id, self.renderer.render_task(task))
+            }
+            OutputFormat::Table => self.renderer.render_task(task),
+            OutputFormat::Compact => format!(
+                "{}  {}  {}  {}",
+                task.id,
+                self.renderer
+                    .paint_due(task, &format!("{:<10}", self.renderer.format_due(task))),
+                self.renderer
+                    .paint_priority(task, &format!("{:<9}", task.priority.to_string())),
+                task.description
+            ),
+            OutputFormat::Json | OutputFormat::Ndjson => {
+                serde_json::to_string(&TaskRecord::from(task)).unwrap_or_default()
+            }
+            OutputFormat::Tsv => {
+                let record = TaskRecord::from(task);
+                let tags = record.tags.join(",");
+                let postponed = record.postponed.to_string();
+                [
+                    record.id,
+                    record.description,
+                    record.due_date,
+                    &record.priority,
+                    record.notes,
+                    &record.status,
+                    record.created.as_deref().unwrap_or_default(),
+                    record.modified.as_deref().unwrap_or_default(),
+                    record.completed.as_deref().unwrap_or_default(),
+                    &tags,
+                    &postponed,
+                    record.rank,
+                    record.remind,
+                ]
+                .iter()
+                .map(|field| escape_tsv(field))
+                .collect::<Vec<_>>()
+                .join("\t")
+            }
+        }
+    }
+
+    // A single task with every field. The tsv format has no room for the
+    // history and prints the usual task line.
+    pub fn format_details(&self, task: &Task) -> String {
+        let record = || DetailRecord {
+            task: TaskRecord::from(task),
+            history: task
+                .history
+                .iter()
+                .map(|entry| HistoryRecord {
+                    at: format_timestamp(entry.at),
+                    action: entry.action.to_string(),
+                    detail: &entry.detail,
+                })
+                .collect(),
+        };
+        match self.format {
+            OutputFormat::Json => serde_json::to_string_pretty(&record()).unwrap_or_default(),
+            OutputFormat::Ndjson => serde_json::to_string(&record()).unwrap_or_default(),
+            OutputFormat::Tsv => self.format_task(task, true),
+            OutputFormat::Table | OutputFormat::Compact => self.renderer.render_details(task),
+        }
+    }
+
+    pub fn format_tasks(&self, tasks: &[Task], with_ids: bool) -> String {
+        match self.format {
+            OutputFormat::Json => {
+                let records: Vec<TaskRecord> = tasks.iter().map(TaskRecord::from).collect();
+                serde_json::to_string_pretty(&records).unwrap_or_default()
+            }
+            _ => tasks
+                .iter()
+                .map(|task| self.format_task(task, with_ids))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    // Renders named groups of tasks, e.g. agenda buckets. Each group is
+    // serialized as `{"group": key, "tasks": [...]}` in the JSON formats and
+    // as a leading column in tsv.
+    pub fn format_groups(&self, groups: &[(&str, &str, Vec<&Task>)]) -> String {
+        match self.format {
+            OutputFormat::Json | OutputFormat::Ndjson => {
+                let records: Vec<GroupRecord> = groups
+                    .iter()
+                    .map(|(key, _, tasks)| GroupRecord {
+                        group: key,
+                        tasks: tasks.iter().map(|t| TaskRecord::from(*t)).collect(),
+                    })
+                    .collect();
+                if self.format == OutputFormat::Json {
+                    serde_json::to_string_pretty(&records).unwrap_or_default()
+                } else {
+                    records
+                        .iter()
+                        .map(|r| serde_json::to_string(r).unwrap_or_default())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            }
+            OutputFormat::Tsv => groups
+                .iter()
+                .flat_map(|(key, _, tasks)| {
+                    tasks
+                        .iter()
+                        .map(move |task| format!("{key}\t{}", self.format_task(task, true)))
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            OutputFormat::Table | OutputFormat::Compact => groups
+                .iter()
+                .map(|(_, title, tasks)| {
+                    let mut lines = vec![format!("== {title} ==")];
+                    lines.extend(tasks.iter().map(|task| self.format_task(task, true)));
+                    lines.join("\n")
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        }
+    }
+
+    pub fn print_tasks(&self, tasks: &[Task], with_ids: bool) {
+        let output = self.format_tasks(tasks, with_ids);
+        if !output.is_empty() {
+            println!("{output}");
+        }
+    }
+
+    // Task lists shown before a prompt go to stderr with the prompt, so
+    // stdout only carries the command's result.
+    pub fn print_choices(&self, tasks: &[Task]) {
+        let output = self.format_tasks(tasks, true);
+        if !output.is_empty() {
+            eprintln!("{output}");
+        }
+    }
+
+    pub fn print_error(&self, code: &str, message: &str) {
+        print_error(self.format, code, message);
+    }
+}
+
+pub fn format_error(format: OutputFormat, code: &str, message: &str) -> String {
+    if format.is_json() {
+        let record = ErrorRecord {
+            error: ErrorBody { code, message },
+        };
+        serde_json::to_string(&record).unwrap_or_default()
+    } else {
+        message.to_string()
+    }
+}
+
+pub fn print_error(format: OutputFormat, code: &str, message: &str) {
+    eprintln!("{}", format_error(format, code, message));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+
+    fn sample_task() -> Task {
+        Task {
+            id: "3D".to_string(),
+            description: "Buy groceries".to_string(),
+            due_date: "01/15/2026".to_string(),
+            priority: Priority::Important,
+            notes: "Milk\tand eggs".to_string(),
+            tags: vec!["errands".to_string()],
+            rank: "i".to_string(),
+            history: vec!["2026-01-02T10:00:00 created".parse().unwrap()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_formats() {
+        assert_eq!("JSON".parse(), Ok(OutputFormat::Json));
+        assert_eq!("ndjson".parse(), Ok(OutputFormat::Ndjson));
+        assert_eq!("tsv".parse(), Ok(OutputFormat::Tsv));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    fn plain(format: OutputFormat) -> Output {
+        Output::new(format, Renderer::default())
+    }
+
+    #[test]
+    fn table_matches_display() {
+        let task = sample_task();
+        assert_eq!(
+            plain(OutputFormat::Table).format_task(&task, false),
+            task.to_string()
+        );
+    }
+
+    #[test]
+    fn json_schema_field_order() {
+        let output = plain(OutputFormat::Ndjson).format_task(&sample_task(), false);
+        assert_eq!(
+            output,
+            r#"{"id":"3D","description":"Buy groceries","due_date":"01/15/2026","priority":"Important","notes":"Milk\tand eggs","status":"Open","created":"2026-01-02T10:00:00","modified":"2026-01-02T10:00:00","completed":null,"tags":["errands"],"postponed":0,"rank":"i","remind":""}"#
+        );
+    }
+
+    #[test]
+    fn details_add_history_to_the_record() {
+        let output = plain(OutputFormat::Ndjson).format_details(&sample_task());
+        assert!(output.ends_with(
+            r#""tags":["errands"],"postponed":0,"rank":"i","remind":"","history":[{"at":"2026-01-02T10:00:00","action":"created","detail":""}]}"#
+        ));
+        assert_eq!(
+            plain(OutputFormat::Tsv).format_details(&sample_task()),
+            plain(OutputFormat::Tsv).format_task(&sample_task(), true)
+        );
+    }
+
+    #[test]
+    fn json_array_for_multiple_tasks() {
+        let tasks = vec![sample_task(), sample_task()];
+        let parsed: serde_json::Value =
+            serde_json::from_str(&plain(OutputFormat::Json).format_tasks(&tasks, false)).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 2);
+        assert_eq!(parsed[0]["id"], "3D");
+    }
+
+    #[test]
+    fn json_empty_list_is_empty_array() {
+        assert_eq!(plain(OutputFormat::Json).format_tasks(&[], false), "[]");
+    }
+
+    #[test]
+    fn groups_in_json_and_tsv() {
+        let task = sample_task();
+        let groups = vec![("overdue", "Overdue", vec![&task])];
+        let parsed: serde_json::Value =
+            serde_json::from_str(&plain(OutputFormat::Json).format_groups(&groups)).unwrap();
+        assert_eq!(parsed[0]["group"], "overdue");
+        assert_eq!(parsed[0]["tasks"][0]["id"], "3D");
+        assert!(plain(OutputFormat::Tsv)
+            .format_groups(&groups)
+            .starts_with("overdue\t3D\t"));
+        assert!(plain(OutputFormat::Table)
+            .format_groups(&groups)
+            .starts_with("== Overdue ==\nID: 3D\n"));
+    }
+
+    #[test]
+    fn tsv_escapes_fields() {
+        let output = plain(OutputFormat::Tsv).format_task(&sample_task(), false);
+        assert_eq!(
+            output,
+            "3D\tBuy groceries\t01/15/2026\tImportant\tMilk\\tand eggs\tOpen\t2026-01-02T10:00:00\t2026-01-02T10:00:00\t\terrands\t0\ti\t"
+        );
+    }
+
+    #[test]
+    fn error_is_structured_for_json() {
+        assert_eq!(
+            format_error(OutputFormat::Json, "not_found", "Task ID 1A not found."),
+            r#"{"error":{"code":"not_found","message":"Task ID 1A not found."}}"#
+        );
+        assert_eq!(
+            format_error(OutputFormat::Table, "not_found", "Task ID 1A not found."),
+            "Task ID 1A not found."
+        );
+    }
+}
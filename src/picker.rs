@@ -0,0 +1,292 @@
+use crate::i18n::tf;
+use crate::models::Task;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+use std::io::IsTerminal;
+
+// Scores `query` as a case-insensitive subsequence of `text`. Consecutive
+// matches and matches at the start of a word score higher. Returns None when
+// the query does not match.
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut previous: Option<usize> = None;
+    for q in query.to_lowercase().chars() {
+        let found = pos + text[pos..].iter().position(|&c| c == q)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+fn haystack(task: &Task) -> String {
+    format!(
+        "{} {} {}",
+        task.description,
+        task.notes,
+        task.tags.join(" ")
+    )
+}
+
+// Every whitespace-separated word of the query has to match. Best matches
+// come first; an empty query keeps the original order.
+pub fn rank<'a>(tasks: &'a [Task], query: &str) -> Vec<&'a Task> {
+    let mut scored: Vec<(i64, usize, &Task)> = tasks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, task)| {
+            let text = haystack(task);
+            query
+                .split_whitespace()
+                .map(|word| fuzzy_score(word, &text))
+                .sum::<Option<i64>>()
+                .map(|score| (score, index, task))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, _, task)| task).collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Step {
+    Continue,
+    Picked(String),
+    Cancelled,
+}
+
+pub struct Picker<'a> {
+    tasks: &'a [Task],
+    title: String,
+    query: String,
+    selected: usize,
+}
+
+impl<'a> Picker<'a> {
+    pub fn new(tasks: &'a [Task], title: &str) -> Self {
+        Picker {
+            tasks,
+            title: title.to_string(),
+            query: String::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn matches(&self) -> Vec<&'a Task> {
+        rank(self.tasks, &self.query)
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Step {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Step::Cancelled,
+            KeyCode::Char('c') if ctrl => return Step::Cancelled,
+            KeyCode::Enter => {
+                return match self.matches().get(self.selected) {
+                    Some(task) => Step::Picked(task.id.clone()),
+                    None => Step::Continue,
+                };
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('p') if ctrl => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Tab => self.selected += 1,
+            KeyCode::Char('n') if ctrl => self.selected += 1,
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        self.selected = self.selected.min(self.matches().len().saturating_sub(1));
+        Step::Continue
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let [input, results] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(1)]).areas(frame.area());
+
+        frame.render_widget(
+            Paragraph::new(format!("> {}_", self.query)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} ", self.title)),
+            ),
+            input,
+        );
+
+        let matches = self.matches();
+        let items: Vec<ListItem> = matches
+            .iter()
+            .map(|task| {
+                let mut spans = vec![
+                    Span::raw(format!("{:<4}", task.id)),
+                    Span::raw(task.description.clone()),
+                ];
+                if !task.tags.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  [{}]", task.tags.join(", ")),
+                        Style::default().add_modifier(Modifier::DIM),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let title = tf(
+            "picker_title",
+            &[("shown", &matches.len()), ("total", &self.tasks.len())],
+        );
+        let title = format!(" {title} ");
+        let mut state = ListState::default();
+        if !matches.is_empty() {
+            state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                .highlight_symbol("> "),
+            results,
+            &mut state,
+        );
+    }
+}
+
+// The picker needs a terminal on both ends; otherwise callers fall back to
+// their plain prompts.
+pub fn is_available() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+// Lets the user pick one of `tasks`. Returns None if they cancelled.
+pub fn pick(tasks: &[Task], title: &str) -> Result<Option<String>, String> {
+    let mut picker = Picker::new(tasks, title);
+    let mut terminal = ratatui::init();
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| picker.draw(frame)) {
+            break Err(e.to_string());
+        }
+        let key = match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => key,
+            Ok(_) => continue,
+            Err(e) => break Err(e.to_string()),
+        };
+        match picker.handle_key(key) {
+            Step::Continue => {}
+            Step::Picked(id) => break Ok(Some(id)),
+            Step::Cancelled => break Ok(None),
+        }
+    };
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn tasks() -> Vec<Task> {
+        let task = |id: &str, description: &str, notes: &str, tags: &[&str]| Task {
+            id: id.to_string(),
+            description: description.to_string(),
+            notes: notes.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+        vec![
+            task("1A", "Write quarterly report", "", &["work"]),
+            task("2B", "Buy milk", "and bread", &["errands"]),
+            task("3C", "Book dentist", "ask about the bill", &[]),
+        ]
+    }
+
+    fn ids(tasks: &[Task], query: &str) -> Vec<String> {
+        rank(tasks, query).iter().map(|t| t.id.clone()).collect()
+    }
+
+    fn type_keys(picker: &mut Picker, keys: &str) {
+        for c in keys.chars() {
+            picker.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn fuzzy_matches_subsequences() {
+        assert!(fuzzy_score("bkdn", "Book dentist").is_some());
+        assert!(fuzzy_score("xyz", "Book dentist").is_none());
+        assert!(fuzzy_score("boo", "Book").unwrap() > fuzzy_score("bok", "Book").unwrap());
+    }
+
+    #[test]
+    fn ranks_over_description_notes_and_tags() {
+        let tasks = tasks();
+        assert_eq!(ids(&tasks, ""), vec!["1A", "2B", "3C"]);
+        assert_eq!(ids(&tasks, "bread"), vec!["2B"]);
+        assert_eq!(ids(&tasks, "errands"), vec!["2B"]);
+        assert_eq!(ids(&tasks, "b"), vec!["2B", "3C"]);
+        assert_eq!(ids(&tasks, "book bill"), vec!["3C"]);
+    }
+
+    #[test]
+    fn navigate_and_confirm() {
+        let tasks = tasks();
+        let mut picker = Picker::new(&tasks, "Complete a task");
+        picker.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        picker.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        picker.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        assert_eq!(
+            picker.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            Step::Picked("3C".to_string())
+        );
+        type_keys(&mut picker, "milk");
+        assert_eq!(
+            picker.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            Step::Picked("2B".to_string())
+        );
+        type_keys(&mut picker, "zz");
+        assert_eq!(
+            picker.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            Step::Continue
+        );
+        assert_eq!(
+            picker.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            Step::Cancelled
+        );
+    }
+
+    #[test]
+    fn draws_query_and_matches() {
+        let tasks = tasks();
+        let mut picker = Picker::new(&tasks, "Complete a task");
+        type_keys(&mut picker, "work");
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+        terminal.draw(|frame| picker.draw(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Complete a task"));
+        assert!(screen.contains("> work_"));
+        assert!(screen.contains("> 1A  Write quarterly report  [work]"));
+        assert!(screen.contains("1/3 tasks"));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::hooks::is_executable;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// External commands work like git's: `todo_cli foo` runs the first
+// `todo_cli-foo` executable found on PATH.
+const PREFIX: &str = "todo_cli-";
+
+pub fn path_dirs() -> Vec<PathBuf> {
+    env::var_os("PATH")
+        .map(|path| env::split_paths(&path).collect())
+        .unwrap_or_default()
+}
+
+pub fn find(command: &str) -> Option<PathBuf> {
+    find_in(&path_dirs(), command)
+}
+
+fn find_in(dirs: &[PathBuf], command: &str) -> Option<PathBuf> {
+    // A command name must not reach outside the PATH directories.
+    if command.is_empty() || command.contains(['/', '\\']) || command.starts_with('.') {
+        return None;
+    }
+    dirs.iter()
+        .map(|dir| dir.join(format!("{PREFIX}{command}")))
+        .find(|path| is_executable(path))
+}
+
+// The command names of every plugin on PATH, for `help`.
+pub fn list() -> Vec<String> {
+    list_in(&path_dirs())
+}
+
+fn list_in(dirs: &[PathBuf]) -> Vec<String> {
+    let names: BTreeSet<String> = dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| is_executable(&entry.path()))
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_prefix(PREFIX)
+                .filter(|command| !command.is_empty())
+                .map(str::to_string)
+        })
+        .collect();
+    names.into_iter().collect()
+}
+
+// The task dump is only readable by the user, and is created fresh so an
+// existing file or link in the temp directory is never written through.
+fn write_dump(json: &str) -> Option<PathBuf> {
+    let path = env::temp_dir().join(format!("todo_cli-tasks-{}.json", std::process::id()));
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path).ok()?;
+    if file.write_all(json.as_bytes()).is_err() {
+        let _ = fs::remove_file(&path);
+        return None;
+    }
+    Some(path)
+}
+
+// Runs the plugin with the terminal attached and returns its exit code. When
+// `tasks` is given it is written to a file named by `TODO_CLI_TASKS`, which is
+// removed again afterwards. A plugin killed by a signal counts as a failure.
+pub fn run(
+    path: &Path,
+    args: &[String],
+    mut vars: Vec<(&str, String)>,
+    tasks: Option<&str>,
+) -> Result<i32, String> {
+    let dump = tasks.and_then(write_dump);
+    if let Some(dump) = &dump {
+        vars.push(("TODO_CLI_TASKS", dump.display().to_string()));
+    }
+    let status = Command::new(path).args(args).envs(vars).status();
+    if let Some(dump) = dump {
+        let _ = fs::remove_file(dump);
+    }
+    Ok(status.map_err(|e| e.to_string())?.code().unwrap_or(1))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn plugin_dir(name: &str, plugins: &[&str]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("todo_cli_plugins_{}_{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for plugin in plugins {
+            let path = dir.join(plugin);
+            fs::write(&path, "#!/bin/sh\nexit 3\n").unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        fs::write(dir.join("todo_cli-notes.txt"), "not executable").unwrap();
+        dir
+    }
+
+    #[test]
+    fn finds_and_lists_plugins_on_path() {
+        let first = plugin_dir("first", &["todo_cli-sync", "todo_cli-", "other"]);
+        let second = plugin_dir("second", &["todo_cli-sync", "todo_cli-burndown"]);
+        let dirs = vec![first.clone(), second];
+
+        assert_eq!(find_in(&dirs, "sync"), Some(first.join("todo_cli-sync")));
+        assert_eq!(find_in(&dirs, "notes.txt"), None);
+        assert_eq!(find_in(&dirs, "../first/todo_cli-sync"), None);
+        assert_eq!(find_in(&dirs, ""), None);
+        assert_eq!(list_in(&dirs), vec!["burndown", "sync"]);
+
+        let sync = find_in(&dirs, "sync").unwrap();
+        assert_eq!(run(&sync, &[], Vec::new(), Some("[]")), Ok(3));
+    }
+}
@@ -0,0 +1,160 @@
+// Manual order is kept with fractional rank keys: strings of base-36 digits
+// compared as plain strings, like decimal fractions after the point. There is
+// always room between two keys, so moving a task only ever changes its own
+// key. Keys never end in '0', which keeps that room.
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE: usize = DIGITS.len();
+
+// Where `move` puts a task.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Place {
+    Top,
+    Bottom,
+    Before(String),
+    After(String),
+}
+
+fn digit(c: u8) -> usize {
+    DIGITS.iter().position(|&d| d == c).unwrap_or(0)
+}
+
+// A key between `low` and `high`, where a missing `high` is unbounded.
+fn midpoint(low: &str, high: Option<&str>) -> String {
+    let (low, mut high) = (low.as_bytes(), high.map(str::as_bytes));
+    let mut key = Vec::new();
+    let mut i = 0;
+    loop {
+        let l = low.get(i).map_or(0, |&c| digit(c));
+        let h = high.map_or(BASE, |high| high.get(i).map_or(0, |&c| digit(c)));
+        if l == h {
+            key.push(DIGITS[l]);
+        } else if h - l > 1 {
+            key.push(DIGITS[(l + h) / 2]);
+            break;
+        } else if high.is_some_and(|high| high.len() > i + 1) {
+            // `high` cut short after this digit is still above `low`.
+            key.push(DIGITS[h]);
+            break;
+        } else {
+            // Same digit as `low`, then anything above the rest of it.
+            key.push(DIGITS[l]);
+            high = None;
+        }
+        i += 1;
+    }
+    String::from_utf8(key).unwrap_or_default()
+}
+
+// Counting up or down at the first digit that allows it keeps keys short
+// when tasks keep being moved to the bottom or the top.
+fn after(low: &str) -> String {
+    match low.bytes().position(|c| digit(c) < BASE - 1) {
+        Some(i) => format!(
+            "{}{}",
+            &low[..i],
+            DIGITS[digit(low.as_bytes()[i]) + 1] as char
+        ),
+        None => midpoint(low, None),
+    }
+}
+
+fn before(high: &str) -> String {
+    match high.bytes().position(|c| digit(c) > 1) {
+        Some(i) => format!(
+            "{}{}",
+            &high[..i],
+            DIGITS[digit(high.as_bytes()[i]) - 1] as char
+        ),
+        None => midpoint("", Some(high)),
+    }
+}
+
+// A key that sorts after `low` and before `high`. Keys that are out of order,
+// which only hand-edited files can have, are treated as if `high` were
+// missing.
+pub fn between(low: Option<&str>, high: Option<&str>) -> String {
+    match (low, high) {
+        (None, None) => midpoint("", None),
+        (Some(low), None) => after(low),
+        (None, Some(high)) => before(high),
+        (Some(low), Some(high)) if low < high => midpoint(low, Some(high)),
+        (Some(low), Some(_)) => after(low),
+    }
+}
+
+// `count` keys of equal length spread evenly over the whole range, for giving
+// a list of unranked tasks their order in one go.
+pub fn spread(count: usize) -> Vec<String> {
+    let mut width = 1;
+    while BASE.pow(width) <= count {
+        width += 1;
+    }
+    let range = BASE.pow(width);
+    (1..=count)
+        .map(|n| {
+            let mut value = n * range / (count + 1);
+            let mut key = vec![b'0'; width as usize];
+            for slot in key.iter_mut().rev() {
+                *slot = DIGITS[value % BASE];
+                value /= BASE;
+            }
+            let key = String::from_utf8(key).unwrap_or_default();
+            key.trim_end_matches('0').to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_between(low: Option<&str>, high: Option<&str>) -> String {
+        let key = between(low, high);
+        assert!(!key.ends_with('0'), "{key}");
+        assert!(low.is_none_or(|low| low < key.as_str()), "{low:?} < {key}");
+        assert!(
+            high.is_none_or(|high| key.as_str() < high),
+            "{key} < {high:?}"
+        );
+        key
+    }
+
+    #[test]
+    fn keys_fall_between_their_neighbours() {
+        assert_eq!(between(None, None), "i");
+        assert_eq!(assert_between(Some("i"), None), "j");
+        assert_eq!(assert_between(None, Some("i")), "h");
+        assert_eq!(assert_between(Some("a"), Some("c")), "b");
+        assert_eq!(assert_between(Some("a"), Some("b")), "ai");
+        assert_eq!(assert_between(Some("a"), Some("b1")), "b");
+        assert_eq!(assert_between(Some("az"), Some("b")), "azi");
+        assert_eq!(assert_between(Some("zz"), None), "zzi");
+        assert_eq!(assert_between(None, Some("1")), "0i");
+        assert_eq!(assert_between(None, Some("01")), "00i");
+        assert_eq!(between(Some("c"), Some("a")), "d");
+    }
+
+    #[test]
+    fn repeated_inserts_keep_order() {
+        let mut low = "i".to_string();
+        let high = "j".to_string();
+        for _ in 0..100 {
+            let key = assert_between(Some(&low), Some(&high));
+            low = key;
+        }
+        let mut top = "i".to_string();
+        for _ in 0..100 {
+            top = assert_between(None, Some(&top));
+        }
+        assert!(top.len() < 10, "{top}");
+    }
+
+    #[test]
+    fn spread_keys_are_ordered() {
+        assert_eq!(spread(1), vec!["i"]);
+        assert_eq!(spread(3), vec!["9", "i", "r"]);
+        let keys = spread(500);
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(keys.iter().all(|key| key.len() <= 2 && !key.ends_with('0')));
+    }
+}
@@ -0,0 +1,340 @@
+use crate::dates::{
+    format_timestamp, parse_duration, parse_stored_date, parse_timestamp, DatePattern,
+};
+use crate::i18n::{read_error, t, tf, write_error};
+use crate::models::{Status, Task};
+use crate::plugin;
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Reminder offsets further from the due time than this are refused.
+const MAX_OFFSET_DAYS: i64 = 366;
+
+// A task's `remind` field is either an offset from its due time, such as
+// `-1h` or `-2d`, or an absolute `YYYY-MM-DDTHH:MM:SS` timestamp.
+pub fn parse(input: &str, dates: &DatePattern) -> Result<String, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(String::new());
+    }
+    if let Some(span) = offset(input) {
+        if span.num_days().abs() > MAX_OFFSET_DAYS {
+            return Err(tf(
+                "reminder_offset_too_large",
+                &[("input", &input), ("days", &MAX_OFFSET_DAYS)],
+            ));
+        }
+        return Ok(input.to_lowercase());
+    }
+    let invalid = || tf("invalid_reminder", &[("input", &input), ("format", dates)]);
+    let (date, time) = input.rsplit_once(' ').ok_or_else(invalid)?;
+    let date = dates.parse_input(date).map_err(|()| invalid())?;
+    let time = NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| invalid())?;
+    let date = parse_stored_date(&date).ok_or_else(invalid)?;
+    Ok(format_timestamp(date.and_time(time)))
+}
+
+fn offset(remind: &str) -> Option<Duration> {
+    let span = parse_duration(remind.get(1..)?)?;
+    match remind.chars().next()? {
+        '-' => Some(-span),
+        '+' => Some(span),
+        _ => None,
+    }
+}
+
+// When the task's reminder fires. Offsets count from `due_time` on the due
+// date, so a task without a due date has no relative reminder.
+pub fn reminder_time(task: &Task, due_time: NaiveTime) -> Option<NaiveDateTime> {
+    if task.remind.is_empty() {
+        return None;
+    }
+    parse_timestamp(&task.remind).or_else(|| {
+        task.due()?
+            .and_time(due_time)
+            .checked_add_signed(offset(&task.remind)?)
+    })
+}
+
+// When `notify` and exported schedules remind about a task: at its reminder,
+// or else at `due_time` on its due date.
+pub fn alert_time(task: &Task, due_time: NaiveTime) -> Option<NaiveDateTime> {
+    reminder_time(task, due_time).or_else(|| Some(task.due()?.and_time(due_time)))
+}
+
+// How `show` prints the reminder: the offset as typed, and the time it
+// works out to.
+pub fn display(task: &Task, dates: &DatePattern, due_time: NaiveTime) -> String {
+    let format = |at: NaiveDateTime| format!("{} {}", dates.format(at.date()), at.format("%H:%M"));
+    match (parse_timestamp(&task.remind), reminder_time(task, due_time)) {
+        (Some(at), _) => format(at),
+        (None, Some(at)) => format!("{} ({})", task.remind, format(at)),
+        (None, None) => task.remind.clone(),
+    }
+}
+
+// The reminders that have fired, one per line. A key names the task by its ID
+// and creation time, since IDs are reused, plus the time the reminder was
+// set for, so changing a reminder arms it again.
+pub struct State {
+    path: PathBuf,
+    fired: BTreeSet<String>,
+}
+
+fn task_key(task: &Task) -> String {
+    let created = task.created_at().map(format_timestamp).unwrap_or_default();
+    format!("{} {created}", task.id)
+}
+
+fn key(task: &Task, at: NaiveDateTime) -> String {
+    format!("{} {}", task_key(task), format_timestamp(at))
+}
+
+// Kept next to the database, so each database has its own.
+pub fn state_path(database: &str) -> PathBuf {
+    PathBuf::from(format!("{database}.reminders"))
+}
+
+impl State {
+    pub fn load(path: &Path) -> Result<State, String> {
+        let fired = match fs::read_to_string(path) {
+            Ok(text) => text
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeSet::new(),
+            Err(e) => return Err(read_error(path, e)),
+        };
+        Ok(State {
+            path: path.to_path_buf(),
+            fired,
+        })
+    }
+
+    pub fn has_fired(&self, task: &Task, at: NaiveDateTime) -> bool {
+        self.fired.contains(&key(task, at))
+    }
+
+    pub fn mark_fired(&mut self, task: &Task, at: NaiveDateTime) {
+        self.fired.insert(key(task, at));
+    }
+
+    // Forgets reminders of tasks that are no longer in the database at all,
+    // so the file does not grow forever.
+    pub fn prune(&mut self, tasks: &[Task]) {
+        let known: BTreeSet<String> = tasks.iter().map(task_key).collect();
+        self.fired.retain(|line| {
+            line.rsplit_once(' ')
+                .is_some_and(|(task, _)| known.contains(task))
+        });
+    }
+
+    // Written to a temporary file first, so a crash never leaves a
+    // half-written state that would make reminders fire again.
+    pub fn save(&self) -> Result<(), String> {
+        let temp = self.path.with_extension("reminders.tmp");
+        let text: String = self.fired.iter().map(|line| format!("{line}\n")).collect();
+        fs::write(&temp, text)
+            .and_then(|()| fs::rename(&temp, &self.path))
+            .map_err(|e| write_error(&self.path, e))
+    }
+}
+
+// Open tasks whose reminder time has come and that have not fired yet, in
+// the order they were due to fire.
+pub fn due<'a>(
+    tasks: &'a [Task],
+    state: &State,
+    now: NaiveDateTime,
+    due_time: NaiveTime,
+) -> Vec<(&'a Task, NaiveDateTime)> {
+    let mut due: Vec<(&Task, NaiveDateTime)> = tasks
+        .iter()
+        .filter(|task| task.status == Status::Open)
+        .filter_map(|task| Some((task, reminder_time(task, due_time)?)))
+        .filter(|(task, at)| *at <= now && !state.has_fired(task, *at))
+        .collect();
+    due.sort_by_key(|(_, at)| *at);
+    due
+}
+
+// The next reminder still to fire, to know how long the daemon may sleep.
+pub fn next(tasks: &[Task], now: NaiveDateTime, due_time: NaiveTime) -> Option<NaiveDateTime> {
+    tasks
+        .iter()
+        .filter(|task| task.status == Status::Open)
+        .filter_map(|task| reminder_time(task, due_time))
+        .filter(|at| *at > now)
+        .min()
+}
+
+fn on_path(program: &str) -> bool {
+    plugin::path_dirs()
+        .iter()
+        .any(|dir| crate::hooks::is_executable(&dir.join(program)))
+}
+
+// How notifications are shown. `auto` uses the desktop notification service
+// on the session bus when there is one, then `notify-send`, and otherwise
+// prints the reminder. Any other value is a command that is run with the
+// title and the text as its last two arguments.
+pub fn notify(command: &str, title: &str, text: &str) -> Result<(), String> {
+    let (program, args): (String, Vec<String>) = if command.trim() != "auto" {
+        let mut parts = command.split_whitespace().map(str::to_string);
+        let program = parts.next().ok_or(t("reminder_command_empty"))?;
+        let mut args: Vec<String> = parts.collect();
+        args.extend([title.to_string(), text.to_string()]);
+        (program, args)
+    } else if env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some() && on_path("gdbus") {
+        let args = [
+            "call",
+            "--session",
+            "--dest=org.freedesktop.Notifications",
+            "--object-path=/org/freedesktop/Notifications",
+            "--method=org.freedesktop.Notifications.Notify",
+            "todo_cli",
+            "0",
+            "",
+            title,
+            text,
+            "[]",
+            "{}",
+            "-1",
+        ];
+        ("gdbus".to_string(), args.map(str::to_string).to_vec())
+    } else if on_path("notify-send") {
+        (
+            "notify-send".to_string(),
+            vec![title.to_string(), text.to_string()],
+        )
+    } else {
+        println!("{title}: {text}");
+        return Ok(());
+    };
+
+    let output = Command::new(&program)
+        .args(&args)
+        .output()
+        .map_err(|e| tf("error_running", &[("program", &program), ("error", &e)]))?;
+    if !output.status.success() {
+        let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(tf(
+            "command_exited",
+            &[
+                ("program", &program),
+                ("status", &output.status),
+                ("error", &reason),
+            ],
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn nine() -> NaiveTime {
+        NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+    }
+
+    fn task(id: &str, due_date: &str, remind: &str) -> Task {
+        let mut task = Task {
+            id: id.to_string(),
+            description: format!("Task {id}"),
+            due_date: due_date.to_string(),
+            remind: remind.to_string(),
+            ..Default::default()
+        };
+        task.history
+            .push("2026-10-01T08:00:00 created".parse().unwrap());
+        task
+    }
+
+    #[test]
+    fn parses_offsets_and_times() {
+        let dates = DatePattern::default();
+        assert_eq!(parse("-1H", &dates), Ok("-1h".to_string()));
+        assert_eq!(parse("+2d", &dates), Ok("+2d".to_string()));
+        assert_eq!(
+            parse("10/20/2026 07:30", &dates),
+            Ok("2026-10-20T07:30:00".to_string())
+        );
+        assert_eq!(parse(" ", &dates), Ok(String::new()));
+        assert!(parse("-1y", &dates).is_err());
+        assert_eq!(parse("-52w", &dates), Ok("-52w".to_string()));
+        assert!(parse("-367d", &dates).is_err());
+        assert!(parse("-100000000d", &dates).is_err());
+        assert!(parse("10/20/2026", &dates).is_err());
+        assert!(parse("10/20/2026 25:00", &dates).is_err());
+    }
+
+    #[test]
+    fn reminder_times_follow_the_due_date() {
+        assert_eq!(
+            reminder_time(&task("1A", "2026-10-20", "-1h"), nine()),
+            Some(at(20, 8))
+        );
+        assert_eq!(
+            reminder_time(&task("1A", "2026-10-20", "-1d"), nine()),
+            Some(at(19, 9))
+        );
+        assert_eq!(reminder_time(&task("1A", "", "-1h"), nine()), None);
+        assert_eq!(
+            reminder_time(&task("1A", "", "2026-10-18T07:00:00"), nine()),
+            Some(at(18, 7))
+        );
+        assert_eq!(reminder_time(&task("1A", "2026-10-20", ""), nine()), None);
+        // Saved before offsets were limited; too far out to have a time.
+        let stale = task("1A", "2026-10-20", "-100000000d");
+        assert_eq!(reminder_time(&stale, nine()), None);
+        assert_eq!(alert_time(&stale, nine()), Some(at(20, 9)));
+    }
+
+    #[test]
+    fn reminders_fire_once() {
+        let path = std::env::temp_dir().join(format!("todo_cli_reminders_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut tasks = vec![
+            task("1A", "2026-10-20", "-1h"),
+            task("2B", "2026-10-20", "-1d"),
+            task("3C", "2026-10-25", "-1d"),
+        ];
+        tasks[1].status = Status::Done;
+
+        let mut state = State::load(&path).unwrap();
+        let ids: Vec<&str> = due(&tasks, &state, at(20, 8), nine())
+            .iter()
+            .map(|(task, _)| task.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1A"]);
+        state.mark_fired(&tasks[0], at(20, 8));
+        state.save().unwrap();
+        assert_eq!(next(&tasks, at(20, 8), nine()), Some(at(24, 9)));
+
+        let mut state = State::load(&path).unwrap();
+        assert!(due(&tasks, &state, at(20, 8), nine()).is_empty());
+
+        // Moving the reminder arms it again.
+        tasks[0].remind = "-2h".to_string();
+        assert_eq!(due(&tasks, &state, at(20, 8), nine()).len(), 1);
+
+        state.prune(&tasks[1..]);
+        assert!(!state.has_fired(&task("1A", "2026-10-20", "-1h"), at(20, 8)));
+        fs::remove_file(&path).unwrap();
+    }
+}
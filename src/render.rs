@@ -0,0 +1,407 @@
+use crate::dates::{format_timestamp, today, DatePattern};
+use crate::i18n::{t, tf};
+use crate::models::{Priority, Status, Task};
+use crate::reminders;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            other => Err(tf("unknown_color_mode", &[("mode", &other)])),
+        }
+    }
+}
+
+impl ColorChoice {
+    // `--color=always` wins over everything; in auto mode color is only used
+    // on a terminal and only when NO_COLOR is unset or empty.
+    pub fn resolve(self, is_tty: bool, no_color: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => is_tty && !no_color,
+        }
+    }
+
+    pub fn resolve_for_stdout(self) -> bool {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        self.resolve(std::io::stdout().is_terminal(), no_color)
+    }
+}
+
+// Each entry is an ANSI SGR parameter list such as "1;31". An empty entry
+// leaves that element unstyled.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Theme {
+    pub asap: String,
+    pub important: String,
+    pub medium: String,
+    pub minor: String,
+    pub none: String,
+    pub overdue: String,
+    pub due_today: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            asap: "1;31".to_string(),
+            important: "33".to_string(),
+            medium: "36".to_string(),
+            minor: "34".to_string(),
+            none: String::new(),
+            overdue: "1;97;41".to_string(),
+            due_today: "1;30;43".to_string(),
+        }
+    }
+}
+
+impl Theme {
+    pub fn builtin(name: &str) -> Option<Theme> {
+        match name {
+            "default" => Some(Theme::default()),
+            "mono" => Some(Theme {
+                asap: "1".to_string(),
+                important: "1".to_string(),
+                medium: String::new(),
+                minor: "2".to_string(),
+                none: "2".to_string(),
+                overdue: "7".to_string(),
+                due_today: "4".to_string(),
+            }),
+            "pastel" => Some(Theme {
+                asap: "91".to_string(),
+                important: "93".to_string(),
+                medium: "96".to_string(),
+                minor: "94".to_string(),
+                none: "37".to_string(),
+                overdue: "95".to_string(),
+                due_today: "92".to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    // Parses a theme spec: an optional built-in base theme followed by
+    // `element=SGR` overrides, all comma separated, e.g. "mono,overdue=1;31".
+    pub fn parse(spec: &str) -> Result<Theme, String> {
+        let mut theme = Theme::default();
+        for item in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let Some((key, code)) = item.split_once('=') else {
+                theme =
+                    Theme::builtin(item).ok_or_else(|| tf("unknown_theme", &[("theme", &item)]))?;
+                continue;
+            };
+            let code = code.trim();
+            if !code.chars().all(|c| c.is_ascii_digit() || c == ';') {
+                return Err(tf("invalid_color_code", &[("code", &code), ("key", &key)]));
+            }
+            let slot = match key.trim() {
+                "asap" => &mut theme.asap,
+                "important" => &mut theme.important,
+                "medium" => &mut theme.medium,
+                "minor" => &mut theme.minor,
+                "none" => &mut theme.none,
+                "overdue" => &mut theme.overdue,
+                "due_today" => &mut theme.due_today,
+                other => return Err(tf("unknown_theme_element", &[("element", &other)])),
+            };
+            *slot = code.to_string();
+        }
+        Ok(theme)
+    }
+
+    pub fn priority_style(&self, priority: &Priority) -> &str {
+        match priority {
+            Priority::Asap => &self.asap,
+            Priority::Important => &self.important,
+            Priority::Medium => &self.medium,
+            Priority::Minor => &self.minor,
+            Priority::None => &self.none,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Renderer {
+    pub enabled: bool,
+    pub theme: Theme,
+    pub today: NaiveDate,
+    pub date_format: DatePattern,
+    pub separator_width: usize,
+    pub due_time: NaiveTime,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Renderer::new(false, Theme::default())
+    }
+}
+
+impl Renderer {
+    pub fn new(enabled: bool, theme: Theme) -> Self {
+        Renderer {
+            enabled,
+            theme,
+            today: today(),
+            date_format: DatePattern::default(),
+            separator_width: 40,
+            due_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default(),
+        }
+    }
+
+    pub fn format_due(&self, task: &Task) -> String {
+        self.date_format.display(&task.due_date)
+    }
+
+    pub fn paint(&self, style: &str, text: &str) -> String {
+        if !self.enabled || style.is_empty() || text.is_empty() {
+            text.to_string()
+        } else {
+            format!("\x1b[{style}m{text}\x1b[0m")
+        }
+    }
+
+    pub fn due_style(&self, task: &Task) -> &str {
+        match task.due() {
+            Some(due) if due < self.today => &self.theme.overdue,
+            Some(due) if due == self.today => &self.theme.due_today,
+            _ => "",
+        }
+    }
+
+    pub fn paint_priority(&self, task: &Task, text: &str) -> String {
+        self.paint(self.theme.priority_style(&task.priority), text)
+    }
+
+    pub fn paint_due(&self, task: &Task, text: &str) -> String {
+        self.paint(self.due_style(task), text)
+    }
+
+    // Mirrors `Task`'s `Display` impl. With color disabled and the default
+    // date format and separator width the output is exactly `task.to_string()`.
+    pub fn render_task(&self, task: &Task) -> String {
+        let sep = "─".repeat(self.separator_width);
+        let title = if task.priority == Priority::None {
+            task.description.clone()
+        } else {
+            format!("{} ({})", task.description, task.priority)
+        };
+        let mut lines = vec![sep.clone(), self.paint_priority(task, &title)];
+        if !task.notes.is_empty() {
+            lines.push(format!("\"{}\"", task.notes));
+        }
+        lines.push(self.paint_due(task, &self.format_due(task)));
+        let postponed = task.postpone_count();
+        if postponed > 0 {
+            lines.push(tf("postponed_times", &[("count", &postponed)]));
+        }
+        lines.push(sep);
+        lines.join("\n")
+    }
+
+    // Every field of a task as labelled lines, for `show`. Fields without a
+    // value are left out; notes and history follow as indented blocks.
+    pub fn render_details(&self, task: &Task) -> String {
+        let timestamp = |at: Option<NaiveDateTime>| at.map(format_timestamp).unwrap_or_default();
+        let status = match task.status {
+            Status::Open => "status_open",
+            Status::Done => "status_done",
+            Status::Deleted => "status_deleted",
+        };
+        let fields = [
+            ("field_id", task.id.clone(), ""),
+            ("field_description", task.description.clone(), ""),
+            ("field_status", t(status).to_string(), ""),
+            (
+                "field_priority",
+                task.priority.to_string(),
+                self.theme.priority_style(&task.priority),
+            ),
+            ("field_due", self.format_due(task), self.due_style(task)),
+            ("field_tags", task.tags.join(", "), ""),
+            (
+                "field_remind",
+                reminders::display(task, &self.date_format, self.due_time),
+                "",
+            ),
+            ("field_postponed", task.postpone_count().to_string(), ""),
+            ("field_created", timestamp(task.created_at()), ""),
+            ("field_modified", timestamp(task.modified_at()), ""),
+            ("field_completed", timestamp(task.completed_at()), ""),
+            // A restored task keeps its deletion in the history only.
+            (
+                "field_deleted",
+                match task.status {
+                    Status::Deleted => timestamp(task.deleted_at()),
+                    _ => String::new(),
+                },
+                "",
+            ),
+        ];
+        let label = |key: &str| format!("{}:", t(key));
+        let width = fields
+            .iter()
+            .map(|(key, _, _)| label(key).chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut lines: Vec<String> = fields
+            .iter()
+            .filter(|(_, value, _)| !value.is_empty())
+            .map(|(key, value, style)| {
+                format!("{:<width$} {}", label(key), self.paint(style, value))
+            })
+            .collect();
+        if !task.notes.is_empty() {
+            lines.push(label("field_notes"));
+            lines.extend(task.notes.lines().map(|line| format!("  {line}")));
+        }
+        if !task.history.is_empty() {
+            lines.push(label("field_history"));
+            lines.extend(task.history.iter().map(|entry| format!("  {entry}")));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task_due(due_date: &str, priority: Priority) -> Task {
+        Task {
+            id: "1A".to_string(),
+            description: "Buy groceries".to_string(),
+            due_date: due_date.to_string(),
+            priority,
+            notes: "Pick up milk".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn renderer(enabled: bool) -> Renderer {
+        Renderer {
+            enabled,
+            theme: Theme::default(),
+            today: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn color_choice_resolution() {
+        assert!(ColorChoice::Always.resolve(false, true));
+        assert!(!ColorChoice::Never.resolve(true, false));
+        assert!(ColorChoice::Auto.resolve(true, false));
+        assert!(!ColorChoice::Auto.resolve(false, false));
+        assert!(!ColorChoice::Auto.resolve(true, true));
+    }
+
+    #[test]
+    fn disabled_output_is_byte_identical_to_display() {
+        for due in ["01/14/2026", "01/15/2026", "02/01/2026", "not a date"] {
+            let task = task_due(due, Priority::Asap);
+            assert_eq!(renderer(false).render_task(&task), task.to_string());
+        }
+    }
+
+    #[test]
+    fn enabled_output_strips_to_display() {
+        let task = task_due("01/14/2026", Priority::Important);
+        let colored = renderer(true).render_task(&task);
+        assert!(colored.contains("\x1b["));
+        let stripped = colored
+            .replace("\x1b[0m", "")
+            .replace("\x1b[33m", "")
+            .replace("\x1b[1;97;41m", "");
+        assert_eq!(stripped, task.to_string());
+    }
+
+    #[test]
+    fn overdue_and_today_highlighting() {
+        let r = renderer(true);
+        let overdue = task_due("01/14/2026", Priority::None);
+        let today = task_due("01/15/2026", Priority::None);
+        let later = task_due("01/16/2026", Priority::None);
+        assert!(r.paint_due(&overdue, "x").starts_with("\x1b[1;97;41m"));
+        assert!(r.paint_due(&today, "x").starts_with("\x1b[1;30;43m"));
+        assert_eq!(r.paint_due(&later, "x"), "x");
+    }
+
+    #[test]
+    fn theme_parse_base_and_overrides() {
+        let theme = Theme::parse("mono,overdue=1;31").unwrap();
+        assert_eq!(theme.asap, "1");
+        assert_eq!(theme.overdue, "1;31");
+        assert!(Theme::parse("neon").is_err());
+        assert!(Theme::parse("asap=red").is_err());
+        assert!(Theme::parse("sparkles=1").is_err());
+    }
+
+    #[test]
+    fn render_task_uses_date_format_and_separator_width() {
+        let renderer = Renderer {
+            date_format: "DD.MM.YYYY".parse().unwrap(),
+            separator_width: 3,
+            ..renderer(false)
+        };
+        let task = task_due("01/20/2026", Priority::None);
+        assert_eq!(
+            renderer.render_task(&task),
+            "───\nBuy groceries\n\"Pick up milk\"\n20.01.2026\n───"
+        );
+    }
+
+    #[test]
+    fn render_details_lists_every_field() {
+        let mut task = task_due("2026-01-20", Priority::Important);
+        task.tags = vec!["errands".to_string()];
+        task.history = vec!["2026-01-02T10:00:00 created".parse().unwrap()];
+        assert_eq!(
+            renderer(false).render_details(&task),
+            "ID:          1A\n\
+             Description: Buy groceries\n\
+             Status:      Open\n\
+             Priority:    Important\n\
+             Due:         01/20/2026\n\
+             Tags:        errands\n\
+             Postponed:   0\n\
+             Created:     2026-01-02T10:00:00\n\
+             Modified:    2026-01-02T10:00:00\n\
+             Notes:\n  \
+             Pick up milk\n\
+             History:\n  \
+             2026-01-02T10:00:00 created"
+        );
+    }
+
+    #[test]
+    fn render_task_shows_postpone_count() {
+        let renderer = Renderer {
+            separator_width: 3,
+            ..renderer(false)
+        };
+        let mut task = task_due("2026-01-20", Priority::None);
+        task.record(crate::models::Action::Postponed, "2026-01-18 -> 2026-01-20");
+        task.record(crate::models::Action::Postponed, "2026-01-20 -> 2026-01-22");
+        assert!(renderer
+            .render_task(&task)
+            .ends_with("01/20/2026\nPostponed 2 time(s)\n───"));
+    }
+}
@@ -0,0 +1,369 @@
+use crate::agenda::bucket_for;
+use crate::dates::format_timestamp;
+use crate::filter::{Filter, SortKey};
+use crate::i18n::{t, tf};
+use crate::models::Task;
+use crate::render::Renderer;
+use crate::urgency;
+use chrono::NaiveDate;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Column {
+    Id,
+    Description,
+    Due,
+    Priority,
+    Status,
+    Tags,
+    Notes,
+    Created,
+    Postponed,
+    Urgency,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "id" => Ok(Column::Id),
+            "description" => Ok(Column::Description),
+            "due" => Ok(Column::Due),
+            "priority" => Ok(Column::Priority),
+            "status" => Ok(Column::Status),
+            "tags" => Ok(Column::Tags),
+            "notes" => Ok(Column::Notes),
+            "created" => Ok(Column::Created),
+            "postponed" => Ok(Column::Postponed),
+            "urgency" => Ok(Column::Urgency),
+            other => Err(tf("unknown_column", &[("column", &other)])),
+        }
+    }
+}
+
+impl Column {
+    pub fn label(self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Description => "description",
+            Column::Due => "due",
+            Column::Priority => "priority",
+            Column::Status => "status",
+            Column::Tags => "tags",
+            Column::Notes => "notes",
+            Column::Created => "created",
+            Column::Postponed => "postponed",
+            Column::Urgency => "urgency",
+        }
+    }
+
+    fn value(self, task: &Task, renderer: &Renderer) -> String {
+        match self {
+            Column::Id => task.id.clone(),
+            Column::Description => task.description.clone(),
+            Column::Due => renderer.format_due(task),
+            Column::Priority => task.priority.to_string(),
+            Column::Status => task.status.to_string(),
+            Column::Tags => task.tags.join(", "),
+            Column::Notes => task.notes.replace('\n', " "),
+            Column::Created => task.created_at().map(format_timestamp).unwrap_or_default(),
+            Column::Postponed => task.postpone_count().to_string(),
+            Column::Urgency => format!("{:.2}", urgency::score(task, renderer.today)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GroupBy {
+    Due,
+    Priority,
+    Tag,
+    Status,
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "due" => Ok(GroupBy::Due),
+            "priority" => Ok(GroupBy::Priority),
+            "tag" => Ok(GroupBy::Tag),
+            "status" => Ok(GroupBy::Status),
+            other => Err(tf("unknown_grouping", &[("group", &other)])),
+        }
+    }
+}
+
+impl GroupBy {
+    pub fn label(self) -> &'static str {
+        match self {
+            GroupBy::Due => "due",
+            GroupBy::Priority => "priority",
+            GroupBy::Tag => "tag",
+            GroupBy::Status => "status",
+        }
+    }
+}
+
+// A saved view from the `[reports.NAME]` tables of the config file.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Report {
+    pub name: String,
+    pub filter: String,
+    pub sort: Option<SortKey>,
+    pub columns: Vec<Column>,
+    pub group: Option<GroupBy>,
+}
+
+pub const DEFAULT_COLUMNS: &[Column] = &[
+    Column::Id,
+    Column::Due,
+    Column::Priority,
+    Column::Description,
+];
+
+impl Report {
+    pub fn new(name: &str) -> Report {
+        Report {
+            name: name.to_string(),
+            filter: String::new(),
+            sort: None,
+            columns: DEFAULT_COLUMNS.to_vec(),
+            group: None,
+        }
+    }
+
+    pub fn set(&mut self, field: &str, value: &str) -> Result<(), String> {
+        match field {
+            "filter" => {
+                Filter::check(value)?;
+                self.filter = value.to_string();
+            }
+            "sort" => self.sort = Some(value.parse()?),
+            "columns" => {
+                self.columns = value.split(',').map(str::parse).collect::<Result<_, _>>()?;
+            }
+            "group" => self.group = Some(value.parse()?),
+            other => return Err(tf("unknown_report_field", &[("field", &other)])),
+        }
+        Ok(())
+    }
+
+    // One line for `help`, e.g. `filter "tag:work", sort due, group priority`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.filter.is_empty() {
+            parts.push(format!("filter {:?}", self.filter));
+        }
+        if let Some(sort) = self.sort {
+            parts.push(format!("sort {}", sort.label()));
+        }
+        if let Some(group) = self.group {
+            parts.push(format!("group {}", group.label()));
+        }
+        let columns: Vec<&str> = self.columns.iter().map(|c| c.label()).collect();
+        parts.push(format!("columns {}", columns.join(",")));
+        parts.join(", ")
+    }
+}
+
+// Splits already sorted tasks into (key, title, tasks) groups. Groups follow
+// the natural order of the grouping; a task with several tags appears under
+// each of them.
+pub fn group_tasks<'a>(
+    tasks: &[&'a Task],
+    group: GroupBy,
+    today: NaiveDate,
+) -> Vec<(String, String, Vec<&'a Task>)> {
+    // Sorted on (rank, name): variant order for due buckets, priorities and
+    // statuses, tag names alphabetically with untagged tasks last.
+    let mut keyed: Vec<((u8, String), String, String, &Task)> = Vec::new();
+    for &task in tasks {
+        match group {
+            GroupBy::Due => {
+                let bucket = bucket_for(task.due(), today);
+                let key = bucket.key().to_string();
+                keyed.push(((bucket as u8, String::new()), key, bucket.to_string(), task));
+            }
+            GroupBy::Priority => {
+                let key = task.priority.name().to_lowercase();
+                let rank = task.priority.clone() as u8;
+                keyed.push(((rank, String::new()), key, task.priority.to_string(), task));
+            }
+            GroupBy::Tag if task.tags.is_empty() => keyed.push((
+                (1, String::new()),
+                "untagged".to_string(),
+                t("report_untagged").to_string(),
+                task,
+            )),
+            GroupBy::Tag => {
+                for tag in &task.tags {
+                    keyed.push((
+                        (0, tag.to_lowercase()),
+                        tag.to_lowercase(),
+                        tag.clone(),
+                        task,
+                    ));
+                }
+            }
+            GroupBy::Status => {
+                let title = task.status.to_string();
+                keyed.push((
+                    (task.status as u8, String::new()),
+                    title.to_lowercase(),
+                    title,
+                    task,
+                ));
+            }
+        }
+    }
+    keyed.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut groups: Vec<(String, String, Vec<&Task>)> = Vec::new();
+    for (_, key, title, task) in keyed {
+        match groups.last_mut() {
+            Some((last, _, group)) if *last == key => group.push(task),
+            _ => groups.push((key, title, vec![task])),
+        }
+    }
+    groups
+}
+
+// A header row and one aligned row per task. Due dates and priorities are
+// painted like the other views; padding is applied before painting so colors
+// do not upset the alignment.
+pub fn render_table(tasks: &[&Task], columns: &[Column], renderer: &Renderer) -> String {
+    let rows: Vec<Vec<String>> = tasks
+        .iter()
+        .map(|task| columns.iter().map(|c| c.value(task, renderer)).collect())
+        .collect();
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([column.label().len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let pad = |i: usize, text: &str| {
+        if i + 1 == columns.len() {
+            text.to_string()
+        } else {
+            format!("{text:<width$}", width = widths[i])
+        }
+    };
+    let mut lines = vec![columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| pad(i, &c.label().to_uppercase()))
+        .collect::<Vec<_>>()
+        .join("  ")];
+    for (task, row) in tasks.iter().zip(&rows) {
+        let cells: Vec<String> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let cell = pad(i, &row[i]);
+                match column {
+                    Column::Due => renderer.paint_due(task, &cell),
+                    Column::Priority => renderer.paint_priority(task, &cell),
+                    _ => cell,
+                }
+            })
+            .collect();
+        lines.push(cells.join("  "));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+
+    fn task(id: &str, due_date: &str, priority: Priority, tags: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            description: format!("Task {id}"),
+            due_date: due_date.to_string(),
+            priority,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_report_fields() {
+        let mut report = Report::new("weekly");
+        report.set("filter", "tag:work -priority:none").unwrap();
+        report.set("sort", "due").unwrap();
+        report.set("columns", "id, due,description").unwrap();
+        report.set("group", "priority").unwrap();
+        assert_eq!(
+            report.columns,
+            vec![Column::Id, Column::Due, Column::Description]
+        );
+        assert_eq!(
+            report.summary(),
+            "filter \"tag:work -priority:none\", sort due, group priority, columns id,due,description"
+        );
+        assert!(report.set("columns", "id,size").is_err());
+        assert!(report.set("filter", "size:big").is_err());
+        assert!(report.set("colour", "red").is_err());
+    }
+
+    #[test]
+    fn groups_by_priority_and_tag() {
+        let a = task("1A", "", Priority::Minor, &["work", "home"]);
+        let b = task("2B", "", Priority::Asap, &[]);
+        let c = task("3C", "", Priority::Minor, &["work"]);
+        let tasks = vec![&a, &b, &c];
+        let today = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+
+        let titles = |groups: Vec<(String, String, Vec<&Task>)>| -> Vec<(String, Vec<String>)> {
+            groups
+                .into_iter()
+                .map(|(_, title, tasks)| (title, tasks.iter().map(|t| t.id.clone()).collect()))
+                .collect()
+        };
+        assert_eq!(
+            titles(group_tasks(&tasks, GroupBy::Priority, today)),
+            vec![
+                ("ASAP".to_string(), vec!["2B".to_string()]),
+                (
+                    "Minor".to_string(),
+                    vec!["1A".to_string(), "3C".to_string()]
+                ),
+            ]
+        );
+        assert_eq!(
+            titles(group_tasks(&tasks, GroupBy::Tag, today)),
+            vec![
+                ("home".to_string(), vec!["1A".to_string()]),
+                ("work".to_string(), vec!["1A".to_string(), "3C".to_string()]),
+                ("Untagged".to_string(), vec!["2B".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_aligned_columns() {
+        let a = task("1A", "01/15/2026", Priority::Important, &[]);
+        let b = task("2B", "", Priority::None, &[]);
+        let table = render_table(
+            &[&a, &b],
+            &[Column::Id, Column::Priority, Column::Description],
+            &Renderer::default(),
+        );
+        assert_eq!(
+            table,
+            "ID  PRIORITY   DESCRIPTION\n1A  Important  Task 1A\n2B  None       Task 2B"
+        );
+    }
+}
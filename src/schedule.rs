@@ -0,0 +1,387 @@
+use crate::i18n::{create_error, read_error, remove_error, tf, write_error};
+use chrono::NaiveDateTime;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+// One `todo_cli notify ID` call to schedule.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Entry {
+    pub id: String,
+    pub description: String,
+    pub at: NaiveDateTime,
+}
+
+// Generated entries are recognised by this comment, so a later export only
+// replaces what an earlier one for the same database wrote.
+pub fn marker(database: &Path) -> String {
+    format!("# todo_cli reminders for {}", database.display())
+}
+
+fn end_marker(database: &Path) -> String {
+    format!("# end of todo_cli reminders for {}", database.display())
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn shell_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
+
+// Cron turns an unescaped `%` in a command into a newline, even inside
+// quotes.
+fn cron_quote(word: &str) -> String {
+    shell_quote(word).replace('%', r"\%")
+}
+
+// The crontab block: a line per entry that fires at its minute. Cron has no
+// years, so the line would fire again a year later; `notify` remembers what
+// it has shown, and the next export drops the line anyway.
+pub fn cron_block(entries: &[Entry], database: &Path, dir: &Path, program: &Path) -> String {
+    let mut lines = vec![marker(database)];
+    for entry in entries {
+        lines.push(format!("# {} {}", entry.id, one_line(&entry.description)));
+        lines.push(format!(
+            "{} cd {} && {} notify {}",
+            entry.at.format("%M %H %d %m *"),
+            cron_quote(&dir.display().to_string()),
+            cron_quote(&program.display().to_string()),
+            entry.id
+        ));
+    }
+    lines.push(end_marker(database));
+    lines.join("\n") + "\n"
+}
+
+// Swaps the block an earlier export left in `crontab` for `block`, keeping
+// every other line. An empty block just removes the old one. A block without
+// its end marker is an error, since dropping everything after it would take
+// the user's own lines with it.
+pub fn replace_block(crontab: &str, database: &Path, block: &str) -> Result<String, String> {
+    let (begin, end) = (marker(database), end_marker(database));
+    let mut kept = Vec::new();
+    let mut inside = false;
+    for line in crontab.lines() {
+        if line == begin {
+            inside = true;
+        } else if inside && line == end {
+            inside = false;
+        } else if !inside {
+            kept.push(line);
+        }
+    }
+    if inside {
+        return Err(tf(
+            "crontab_unterminated",
+            &[("marker", &begin), ("end", &end)],
+        ));
+    }
+    let mut text: String = kept.iter().map(|line| format!("{line}\n")).collect();
+    text.push_str(block);
+    Ok(text)
+}
+
+fn run_error(program: &str, error: std::io::Error) -> String {
+    tf("error_running", &[("program", &program), ("error", &error)])
+}
+
+fn read_crontab() -> Result<String, String> {
+    let output = Command::new("crontab")
+        .arg("-l")
+        .output()
+        .map_err(|e| run_error("crontab", e))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+    let error = String::from_utf8_lossy(&output.stderr);
+    // Having no crontab yet is fine; anything else must not be overwritten.
+    if error.contains("no crontab") {
+        Ok(String::new())
+    } else {
+        Err(tf(
+            "command_failed",
+            &[("program", &"crontab -l"), ("error", &error.trim())],
+        ))
+    }
+}
+
+fn write_crontab(text: &str) -> Result<(), String> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| run_error("crontab", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| run_error("crontab", e))?;
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(tf(
+            "command_failed",
+            &[("program", &"crontab"), ("error", &error.trim())],
+        ));
+    }
+    Ok(())
+}
+
+// Updates the crontab file at `file`, or the user's crontab when there is
+// none. Nothing is written when the block did not change.
+pub fn export_cron(block: &str, database: &Path, file: Option<&Path>) -> Result<bool, String> {
+    let current = match file {
+        Some(file) => match fs::read_to_string(file) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(read_error(file, e)),
+        },
+        None => read_crontab()?,
+    };
+    let updated = replace_block(&current, database, block)?;
+    if updated == current {
+        return Ok(false);
+    }
+    match file {
+        Some(file) => fs::write(file, updated).map_err(|e| write_error(file, e))?,
+        None => write_crontab(&updated)?,
+    }
+    Ok(true)
+}
+
+// A stable name part per database, so exports for several databases do not
+// touch each other's units. FNV-1a, as it must not change between builds.
+fn database_hash(database: &Path) -> String {
+    let hash = database
+        .display()
+        .to_string()
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+    format!("{:08x}", hash as u32)
+}
+
+fn systemd_quote(word: &str) -> String {
+    format!(
+        "\"{}\"",
+        word.replace('\\', r"\\")
+            .replace('"', "\\\"")
+            .replace('%', "%%")
+    )
+}
+
+// A timer and a service per entry, named `todo_cli-notify-ID-HASH`.
+pub fn systemd_units(
+    entries: &[Entry],
+    database: &Path,
+    dir: &Path,
+    program: &Path,
+) -> Vec<(String, String)> {
+    let hash = database_hash(database);
+    let marker = marker(database);
+    let mut units = Vec::new();
+    for entry in entries {
+        let name = format!("todo_cli-notify-{}-{hash}", entry.id);
+        let description = tf(
+            "unit_description",
+            &[
+                ("id", &entry.id),
+                ("description", &one_line(&entry.description)),
+            ],
+        )
+        .replace('%', "%%");
+        units.push((
+            format!("{name}.timer"),
+            format!(
+                "{marker}\n[Unit]\nDescription={description}\n\n\
+                 [Timer]\nOnCalendar={}\nPersistent=true\n\n\
+                 [Install]\nWantedBy=timers.target\n",
+                entry.at.format("%Y-%m-%d %H:%M:%S")
+            ),
+        ));
+        units.push((
+            format!("{name}.service"),
+            format!(
+                "{marker}\n[Unit]\nDescription={description}\n\n\
+                 [Service]\nType=oneshot\nWorkingDirectory={}\nExecStart={} notify {}\n",
+                dir.display().to_string().replace('%', "%%"),
+                systemd_quote(&program.display().to_string()),
+                entry.id
+            ),
+        ));
+    }
+    units
+}
+
+// What an export changed, by unit file name.
+#[derive(Debug, PartialEq, Default)]
+pub struct Changes {
+    pub written: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+// Writes the units that are new or changed and removes the ones an earlier
+// export for this database wrote that are no longer wanted, together with
+// their enabled timer links. Files without the marker are never touched.
+pub fn sync_units(
+    dir: &Path,
+    database: &Path,
+    units: &[(String, String)],
+) -> Result<Changes, String> {
+    fs::create_dir_all(dir).map_err(|e| create_error(dir, e))?;
+    let marker = marker(database);
+    let mut changes = Changes::default();
+
+    let mut existing: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| read_error(dir, e))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            fs::read_to_string(path).is_ok_and(|text| text.lines().next() == Some(marker.as_str()))
+        })
+        .collect();
+    existing.sort();
+    for path in existing {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_string();
+        if !units.iter().any(|(unit, _)| *unit == name) {
+            fs::remove_file(&path).map_err(|e| remove_error(&path, e))?;
+            // The link `systemctl enable` made would be left dangling.
+            let _ = fs::remove_file(dir.join("timers.target.wants").join(&name));
+            changes.removed.push(name);
+        }
+    }
+
+    for (name, contents) in units {
+        let path = dir.join(name);
+        if fs::read_to_string(&path).is_ok_and(|text| text == *contents) {
+            continue;
+        }
+        fs::write(&path, contents).map_err(|e| write_error(&path, e))?;
+        changes.written.push(name.clone());
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn entry(id: &str, day: u32) -> Entry {
+        Entry {
+            id: id.to_string(),
+            description: format!("Call\nthe vendor {id}"),
+            at: NaiveDate::from_ymd_opt(2026, 10, day)
+                .unwrap()
+                .and_hms_opt(8, 30, 0)
+                .unwrap(),
+        }
+    }
+
+    fn paths() -> (PathBuf, PathBuf, PathBuf) {
+        (
+            PathBuf::from("/home/me/todo/task_database.xml"),
+            PathBuf::from("/home/me/it's todo"),
+            PathBuf::from("/usr/bin/todo_cli"),
+        )
+    }
+
+    #[test]
+    fn cron_blocks_replace_earlier_ones() {
+        let (database, dir, program) = paths();
+        let block = cron_block(&[entry("1A", 24)], &database, &dir, &program);
+        assert_eq!(
+            block,
+            "# todo_cli reminders for /home/me/todo/task_database.xml\n\
+             # 1A Call the vendor 1A\n\
+             30 08 24 10 * cd '/home/me/it'\\''s todo' && '/usr/bin/todo_cli' notify 1A\n\
+             # end of todo_cli reminders for /home/me/todo/task_database.xml\n"
+        );
+
+        let crontab = format!("MAILTO=me\n{block}0 * * * * backup\n");
+        let other = PathBuf::from("/other.xml");
+        let other_block = cron_block(&[entry("2B", 25)], &other, &dir, &program);
+        let crontab = replace_block(&crontab, &other, &other_block).unwrap();
+
+        let updated = cron_block(&[entry("3C", 26)], &database, &dir, &program);
+        let crontab = replace_block(&crontab, &database, &updated).unwrap();
+        assert_eq!(
+            crontab,
+            format!("MAILTO=me\n0 * * * * backup\n{other_block}{updated}")
+        );
+        assert_eq!(
+            replace_block(&crontab, &database, &updated).unwrap(),
+            crontab
+        );
+        assert!(!replace_block(&crontab, &database, "")
+            .unwrap()
+            .contains("3C"));
+    }
+
+    #[test]
+    fn cron_block_without_end_marker_is_an_error() {
+        let (database, dir, program) = paths();
+        let crontab = format!("{}\n0 * * * * backup\n", marker(&database));
+        let block = cron_block(&[entry("1A", 24)], &database, &dir, &program);
+        assert!(replace_block(&crontab, &database, &block).is_err());
+
+        let file = std::env::temp_dir().join(format!("todo_cli_crontab_{}", std::process::id()));
+        fs::write(&file, &crontab).unwrap();
+        assert!(export_cron(&block, &database, Some(&file)).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), crontab);
+        fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn cron_lines_escape_percent_signs() {
+        let (database, _, _) = paths();
+        let dir = PathBuf::from("/home/me/100%");
+        let program = PathBuf::from("/opt/%bin/todo_cli");
+        let block = cron_block(&[entry("1A", 24)], &database, &dir, &program);
+        assert!(block
+            .contains("30 08 24 10 * cd '/home/me/100\\%' && '/opt/\\%bin/todo_cli' notify 1A\n"));
+    }
+
+    #[test]
+    fn units_are_written_updated_and_pruned() {
+        let (database, dir, program) = paths();
+        let units_dir = std::env::temp_dir().join(format!("todo_cli_units_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&units_dir);
+        fs::create_dir_all(&units_dir).unwrap();
+        fs::write(units_dir.join("mine.timer"), "[Timer]\n").unwrap();
+
+        let units = systemd_units(
+            &[entry("1A", 24), entry("2B", 25)],
+            &database,
+            &dir,
+            &program,
+        );
+        assert!(units[0].1.contains("OnCalendar=2026-10-24 08:30:00"));
+        assert!(units[1]
+            .1
+            .contains("ExecStart=\"/usr/bin/todo_cli\" notify 1A"));
+        let changes = sync_units(&units_dir, &database, &units).unwrap();
+        assert_eq!(changes.written.len(), 4);
+        assert_eq!(
+            sync_units(&units_dir, &database, &units).unwrap(),
+            Changes::default()
+        );
+
+        let units = systemd_units(&[entry("2B", 26)], &database, &dir, &program);
+        let changes = sync_units(&units_dir, &database, &units).unwrap();
+        assert_eq!(changes.written, vec![units[0].0.clone()]);
+        assert_eq!(changes.removed.len(), 2);
+        assert!(changes.removed[0].starts_with("todo_cli-notify-1A-"));
+        assert!(units_dir.join("mine.timer").exists());
+        fs::remove_dir_all(&units_dir).unwrap();
+    }
+}
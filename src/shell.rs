@@ -0,0 +1,325 @@
+use crate::cli::{self, split_line, Args};
+use crate::config::Config;
+use crate::i18n::{t, tf};
+use crate::task_manager::TaskManager;
+use crate::{
+    build_output, execute, is_missing, is_mutating, load_tasks, manage_config, print_help,
+    CommandError,
+};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::{Context, Editor, Helper, Highlighter, Hinter, Validator};
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+const OPTIONS: &[&str] = &[
+    "--format",
+    "--color",
+    "--theme",
+    "--filter",
+    "--set",
+    "--yes",
+    "--editor",
+    "--week",
+    "--weeks",
+    "--older-than",
+    "--sort",
+    "--shift",
+    "--template",
+    "--due",
+    "--replace",
+    "--top",
+    "--bottom",
+    "--before",
+    "--after",
+    "--check",
+    "--daemon",
+    "--cron",
+    "--systemd",
+    "--file",
+    "--overdue",
+    "--local",
+];
+const ID_COMMANDS: &[&str] = &[
+    "show",
+    "clone",
+    "explain",
+    "done",
+    "edit",
+    "snooze",
+    "reschedule",
+    "move",
+    "notify",
+    "delete",
+];
+const TRASH_COMMANDS: &[&str] = &["list", "restore", "empty"];
+const TEMPLATE_COMMANDS: &[&str] = &["list", "save", "delete"];
+const SCHEDULE_COMMANDS: &[&str] = &["export"];
+const CONFIG_COMMANDS: &[&str] = &["list", "get", "set"];
+
+#[derive(Helper, Hinter, Highlighter, Validator, Default)]
+struct ShellHelper {
+    commands: Vec<String>,
+    ids: Vec<String>,
+    trash_ids: Vec<String>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, words) =
+            complete_word(&line[..pos], &self.commands, &self.ids, &self.trash_ids);
+        let pairs = words
+            .into_iter()
+            .map(|word| Pair {
+                display: word.clone(),
+                replacement: word,
+            })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+// Returns where the word under the cursor starts and the candidates for it:
+// command names first, then subcommands, options or task IDs depending on the
+// command. `commands` holds the aliases and reports from the config file.
+fn complete_word(
+    before: &str,
+    commands: &[String],
+    ids: &[String],
+    trash_ids: &[String],
+) -> (usize, Vec<String>) {
+    let start = before
+        .rfind(char::is_whitespace)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let word = &before[start..];
+    let previous: Vec<&str> = before[..start]
+        .split_whitespace()
+        .filter(|w| !w.starts_with("--"))
+        .collect();
+
+    let candidates: Vec<String> = if word.starts_with("--") {
+        OPTIONS.iter().map(|o| o.to_string()).collect()
+    } else {
+        match previous.as_slice() {
+            [] => cli::COMMANDS
+                .iter()
+                .filter(|c| **c != "shell")
+                .chain(&["exit"])
+                .map(|c| c.to_string())
+                .chain(commands.iter().cloned())
+                .collect(),
+            ["trash"] => TRASH_COMMANDS.iter().map(|c| c.to_string()).collect(),
+            ["trash", "restore", ..] => trash_ids.to_vec(),
+            ["template"] => TEMPLATE_COMMANDS.iter().map(|c| c.to_string()).collect(),
+            ["template", "save", _, ..] => ids.to_vec(),
+            ["schedule"] => SCHEDULE_COMMANDS.iter().map(|c| c.to_string()).collect(),
+            ["config"] => CONFIG_COMMANDS.iter().map(|c| c.to_string()).collect(),
+            ["config", "get" | "set"] => crate::config::keys().map(str::to_string).collect(),
+            [command, ..] if ID_COMMANDS.contains(command) => ids.to_vec(),
+            _ => Vec::new(),
+        }
+    };
+
+    let lowered = word.to_lowercase();
+    let matches = candidates
+        .into_iter()
+        .filter(|c| c.to_lowercase().starts_with(&lowered))
+        .collect();
+    (start, matches)
+}
+
+fn modified(filename: &str) -> Option<SystemTime> {
+    fs::metadata(filename).and_then(|m| m.modified()).ok()
+}
+
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".todo_cli_history"))
+}
+
+// Runs commands against one in-memory task list. The file is saved after every
+// mutating command and reloaded whenever it changed on disk in the meantime.
+// `session_args` are options given to `shell` itself, e.g. `--format json`;
+// options on a line override them for that line.
+pub fn run(config: &Config, session_args: &[String]) -> Result<(), CommandError> {
+    let filename = config.database();
+    let mut manager = TaskManager::new();
+    manager.set_id_strategy(config.id_strategy());
+    manager.set_hooks(config.hooks());
+    let mut needs_save = false;
+    if !is_missing(filename) {
+        needs_save = load_tasks(&mut manager, filename)?;
+    }
+    let mut loaded_at = modified(filename);
+
+    let mut editor: Editor<ShellHelper, DefaultHistory> =
+        Editor::new().map_err(|e| CommandError::new("io", e.to_string()))?;
+    editor.set_helper(Some(ShellHelper {
+        commands: config.command_names(),
+        ..Default::default()
+    }));
+    let history = history_path();
+    if let Some(path) = &history {
+        let _ = editor.load_history(path);
+    }
+
+    loop {
+        if let Some(helper) = editor.helper_mut() {
+            helper.ids = manager.fetch_tasks().iter().map(|t| t.id.clone()).collect();
+            helper.trash_ids = manager.fetch_trash().iter().map(|t| t.id.clone()).collect();
+        }
+        let line = match editor.readline("todo> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(CommandError::new("io", e.to_string())),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+
+        let words = match split_line(line) {
+            Ok(words) => [session_args, &words].concat(),
+            Err(e) => {
+                eprintln!("{e}");
+                continue;
+            }
+        };
+        let args = config.expand_alias(Args::parse(&words));
+        let out = match build_output(&args, config) {
+            Ok(out) => out,
+            Err((format, e)) => {
+                crate::output::print_error(format, "usage", &e);
+                continue;
+            }
+        };
+        let Some(command) = args.command.as_deref() else {
+            continue;
+        };
+        match command {
+            "exit" | "quit" => break,
+            "shell" => {
+                println!("{}", t("shell_nested"));
+                continue;
+            }
+            "help" => {
+                print_help(config);
+                println!("{}", t("help_exit"));
+                continue;
+            }
+            // Changed settings take effect in the next session.
+            "config" => {
+                if let Err(e) = manage_config(&args, config, &out) {
+                    out.print_error(e.code, &e.message);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        // Until a changed file reads cleanly again, the tasks in memory are
+        // stale and saving them would undo the change, so `loaded_at` stays
+        // put and the next command tries again.
+        if modified(filename) != loaded_at {
+            match load_tasks(&mut manager, filename) {
+                Ok(stale) => {
+                    needs_save = stale;
+                    loaded_at = modified(filename);
+                    eprintln!("{}", tf("shell_reloaded", &[("file", &filename)]));
+                }
+                Err(e) => {
+                    out.print_error(e.code, &e.message);
+                    if needs_save || is_mutating(command) {
+                        out.print_error("io", &tf("shell_reload_failed", &[("file", &filename)]));
+                        continue;
+                    }
+                }
+            }
+        }
+
+        let result = execute(command, &args, &mut manager, &out, config);
+
+        if needs_save || is_mutating(command) {
+            match manager.save_tasks(filename) {
+                Ok(()) => needs_save = false,
+                Err(e) => out.print_error("io", &tf("error_saving", &[("error", &e)])),
+            }
+            loaded_at = modified(filename);
+        }
+        if let Err(e) = result {
+            out.print_error(e.code, &e.message);
+        }
+    }
+
+    if let Some(path) = &history {
+        let _ = editor.save_history(path);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complete(before: &str) -> (usize, Vec<String>) {
+        let commands = vec!["today".to_string()];
+        let ids = vec!["1A".to_string(), "1B".to_string(), "2C".to_string()];
+        let trash = vec!["9F".to_string()];
+        complete_word(before, &commands, &ids, &trash)
+    }
+
+    #[test]
+    fn completes_commands() {
+        assert_eq!(
+            complete("d"),
+            (0, vec!["done".to_string(), "delete".to_string()])
+        );
+        assert_eq!(complete("  ag"), (2, vec!["agenda".to_string()]));
+        assert_eq!(complete("to"), (0, vec!["today".to_string()]));
+    }
+
+    #[test]
+    fn completes_every_command_the_parser_knows() {
+        for command in cli::COMMANDS.iter().filter(|c| **c != "shell") {
+            assert!(
+                complete(command).1.contains(&command.to_string()),
+                "{command}"
+            );
+        }
+        assert_eq!(complete("ex").1, vec!["explain", "exit"]);
+        assert_eq!(complete("sh").1, vec!["show"]);
+    }
+
+    #[test]
+    fn completes_ids_case_insensitively() {
+        assert_eq!(
+            complete("done 1"),
+            (5, vec!["1A".to_string(), "1B".to_string()])
+        );
+        assert_eq!(complete("edit --editor 2c"), (14, vec!["2C".to_string()]));
+        assert_eq!(complete("view 1").1, Vec::<String>::new());
+    }
+
+    #[test]
+    fn completes_trash_subcommands_and_options() {
+        assert_eq!(complete("trash r").1, vec!["restore"]);
+        assert_eq!(complete("trash restore ").1, vec!["9F"]);
+        assert_eq!(complete("view --fo").1, vec!["--format"]);
+        assert_eq!(complete("config s").1, vec!["set"]);
+        assert_eq!(
+            complete("config get dates.o").1,
+            vec!["dates.output_format"]
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use crate::dates::{format_stored_date, parse_stored_date, DatePattern};
+use crate::i18n::tf;
+use crate::models::{Action, Task};
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+
+// Where a due date moves to: a fixed day (`tomorrow`, `friday`, a date) or an
+// offset from the date it has now (`+2d`, `-1w`, `+1m`).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DueChange {
+    On(NaiveDate),
+    Days(i64),
+    Months(i32),
+}
+
+// Amounts too large for a date to move by are refused here, so `apply` never
+// has to deal with them.
+fn parse_offset(input: &str) -> Option<DueChange> {
+    let sign = match input.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let unit = input.chars().last()?;
+    let digits = input.get(1..input.len() - unit.len_utf8())?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: i64 = digits.parse().ok()?;
+    let (per_unit, months) = match unit {
+        'd' => (1, false),
+        'w' => (7, false),
+        'm' => (1, true),
+        'y' => (12, true),
+        _ => return None,
+    };
+    let amount = amount.checked_mul(per_unit)? * sign;
+    if months {
+        return i32::try_from(amount).ok().map(DueChange::Months);
+    }
+    Duration::try_days(amount)?;
+    Some(DueChange::Days(amount))
+}
+
+// The next given weekday after `today`, a full week ahead when it is today.
+fn next_weekday(weekday: Weekday, today: NaiveDate) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    today + Duration::days(if ahead == 0 { 7 } else { i64::from(ahead) })
+}
+
+impl DueChange {
+    pub fn parse(input: &str, dates: &DatePattern, today: NaiveDate) -> Result<DueChange, String> {
+        let lowered = input.trim().to_lowercase();
+        if let Some(change) = parse_offset(&lowered) {
+            return Ok(change);
+        }
+        match lowered.as_str() {
+            "today" => return Ok(DueChange::On(today)),
+            "tomorrow" => return Ok(DueChange::On(today + Duration::days(1))),
+            _ => {}
+        }
+        if let Ok(weekday) = lowered.parse::<Weekday>() {
+            return Ok(DueChange::On(next_weekday(weekday, today)));
+        }
+        dates
+            .parse_input(input)
+            .ok()
+            .and_then(|date| parse_stored_date(&date))
+            .map(DueChange::On)
+            .ok_or_else(|| tf("invalid_when", &[("input", &input)]))
+    }
+
+    pub fn is_offset(self) -> bool {
+        !matches!(self, DueChange::On(_))
+    }
+
+    pub fn apply(self, from: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            DueChange::On(date) => Some(date),
+            DueChange::Days(days) => from.checked_add_signed(Duration::try_days(days)?),
+            DueChange::Months(months) if months >= 0 => {
+                from.checked_add_months(Months::new(months.unsigned_abs()))
+            }
+            DueChange::Months(months) => {
+                from.checked_sub_months(Months::new(months.unsigned_abs()))
+            }
+        }
+    }
+}
+
+// Moves a task's due date and records it in the history. Moving the date
+// later counts as a postponement; anything else is an ordinary edit.
+// Offsets count from the current due date. With `from_today`, as `snooze`
+// uses, an overdue or missing due date counts from today instead; otherwise a
+// task without a due date cannot be shifted and is left alone. Returns
+// whether the date changed.
+pub fn move_due_date(
+    task: &mut Task,
+    change: DueChange,
+    today: NaiveDate,
+    from_today: bool,
+) -> bool {
+    let current = task.due();
+    let from = match current {
+        Some(due) if from_today => due.max(today),
+        Some(due) => due,
+        None if from_today || !change.is_offset() => today,
+        None => return false,
+    };
+    let Some(new_due) = change.apply(from) else {
+        return false;
+    };
+    if current == Some(new_due) {
+        return false;
+    }
+
+    task.due_date = format_stored_date(new_due);
+    match current {
+        Some(old) if new_due > old => task.record(
+            Action::Postponed,
+            format!("{} -> {}", format_stored_date(old), task.due_date),
+        ),
+        _ => task.record(Action::Edited, "due_date"),
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A Wednesday.
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn parse(input: &str) -> Result<DueChange, String> {
+        DueChange::parse(input, &DatePattern::default(), today())
+    }
+
+    fn task(due_date: &str) -> Task {
+        Task {
+            id: "1A".to_string(),
+            description: "Call the vendor".to_string(),
+            due_date: due_date.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_offsets_and_days() {
+        assert_eq!(parse("+2d"), Ok(DueChange::Days(2)));
+        assert_eq!(parse("-1w"), Ok(DueChange::Days(-7)));
+        assert_eq!(parse("+1m"), Ok(DueChange::Months(1)));
+        assert_eq!(parse("+1y"), Ok(DueChange::Months(12)));
+        assert_eq!(parse("tomorrow"), Ok(DueChange::On(date(2026, 10, 22))));
+        assert_eq!(parse("Friday"), Ok(DueChange::On(date(2026, 10, 23))));
+        assert_eq!(parse("wed"), Ok(DueChange::On(date(2026, 10, 28))));
+        assert_eq!(parse("11/02/2026"), Ok(DueChange::On(date(2026, 11, 2))));
+        assert!(parse("2d").is_err());
+        assert!(parse("+2x").is_err());
+        assert!(parse("someday").is_err());
+    }
+
+    #[test]
+    fn bare_signs_and_huge_offsets_are_refused() {
+        for input in [
+            "+",
+            "-",
+            "+d",
+            "-w",
+            "+-2d",
+            "+200000000000d",
+            "+9999999999999999999w",
+        ] {
+            assert_eq!(
+                parse(input),
+                Err(tf("invalid_when", &[("input", &input)])),
+                "{input}"
+            );
+        }
+        assert_eq!(DueChange::Days(i64::MAX).apply(today()), None);
+        assert_eq!(DueChange::Days(200_000_000_000).apply(today()), None);
+    }
+
+    #[test]
+    fn month_offsets_clamp_to_the_end_of_the_month() {
+        assert_eq!(
+            DueChange::Months(1).apply(date(2026, 1, 31)),
+            Some(date(2026, 2, 28))
+        );
+        assert_eq!(
+            DueChange::Months(-1).apply(date(2026, 3, 31)),
+            Some(date(2026, 2, 28))
+        );
+    }
+
+    #[test]
+    fn postponing_is_counted() {
+        let mut task = task("2026-10-25");
+        assert!(move_due_date(&mut task, DueChange::Days(2), today(), false));
+        assert_eq!(task.due_date, "2026-10-27");
+        assert!(move_due_date(
+            &mut task,
+            DueChange::Days(-1),
+            today(),
+            false
+        ));
+        assert_eq!(task.postpone_count(), 1);
+        assert_eq!(task.history[0].detail, "2026-10-25 -> 2026-10-27");
+        assert_eq!(task.history[1].action, Action::Edited);
+        assert!(!move_due_date(
+            &mut task,
+            DueChange::On(date(2026, 10, 26)),
+            today(),
+            false
+        ));
+    }
+
+    #[test]
+    fn snoozing_counts_from_today_when_overdue() {
+        let mut overdue = task("2026-10-01");
+        assert!(move_due_date(
+            &mut overdue,
+            DueChange::Days(2),
+            today(),
+            true
+        ));
+        assert_eq!(overdue.due_date, "2026-10-23");
+
+        let mut undated = task("");
+        assert!(!move_due_date(
+            &mut undated,
+            DueChange::Days(2),
+            today(),
+            false
+        ));
+        assert!(move_due_date(
+            &mut undated,
+            DueChange::Days(2),
+            today(),
+            true
+        ));
+        assert_eq!(undated.due_date, "2026-10-23");
+        assert_eq!(undated.postpone_count(), 0);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::i18n::{t, tf};
+use crate::models::{Priority, Task};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+
+const PRIORITIES: [Priority; 5] = [
+    Priority::Asap,
+    Priority::Important,
+    Priority::Medium,
+    Priority::Minor,
+    Priority::None,
+];
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PriorityCount {
+    pub priority: String,
+    pub count: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct WeekCount {
+    pub week_start: String,
+    pub count: usize,
+}
+
+// All figures are derived from the tasks' operation history: a task's age is
+// measured from its `created` entry and completions from its `completed`
+// entry. Tasks without those entries (e.g. from older files) are skipped.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Stats {
+    pub open_total: usize,
+    pub open_by_priority: Vec<PriorityCount>,
+    pub overdue: usize,
+    pub average_open_age_days: Option<f64>,
+    pub completed_per_week: Vec<WeekCount>,
+    pub completed_on_time: usize,
+    pub completed_late: usize,
+    pub on_time_rate: Option<f64>,
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+pub fn compute(open: &[Task], archive: &[Task], today: NaiveDate, weeks: usize) -> Stats {
+    let open_by_priority = PRIORITIES
+        .iter()
+        .map(|p| PriorityCount {
+            priority: p.name().to_string(),
+            count: open.iter().filter(|t| &t.priority == p).count(),
+        })
+        .collect();
+
+    let overdue = open
+        .iter()
+        .filter(|t| t.due().is_some_and(|due| due < today))
+        .count();
+
+    let ages: Vec<i64> = open
+        .iter()
+        .filter_map(|t| t.created_at())
+        .map(|created| (today - created.date()).num_days())
+        .collect();
+    let average_open_age_days = if ages.is_empty() {
+        None
+    } else {
+        Some(ages.iter().sum::<i64>() as f64 / ages.len() as f64)
+    };
+
+    let this_week = week_start(today);
+    let completed_per_week = (0..weeks)
+        .rev()
+        .map(|offset| {
+            let start = this_week - Duration::weeks(offset as i64);
+            let count = archive
+                .iter()
+                .filter_map(|t| t.completed_at())
+                .filter(|at| week_start(at.date()) == start)
+                .count();
+            WeekCount {
+                week_start: start.format("%Y-%m-%d").to_string(),
+                count,
+            }
+        })
+        .collect();
+
+    let mut completed_on_time = 0;
+    let mut completed_late = 0;
+    for task in archive {
+        if let (Some(due), Some(completed)) = (task.due(), task.completed_at()) {
+            if completed.date() <= due {
+                completed_on_time += 1;
+            } else {
+                completed_late += 1;
+            }
+        }
+    }
+    let judged = completed_on_time + completed_late;
+    let on_time_rate = (judged > 0).then(|| completed_on_time as f64 / judged as f64);
+
+    Stats {
+        open_total: open.len(),
+        open_by_priority,
+        overdue,
+        average_open_age_days,
+        completed_per_week,
+        completed_on_time,
+        completed_late,
+        on_time_rate,
+    }
+}
+
+pub fn render_text(stats: &Stats) -> String {
+    let mut lines = vec![t("stats_open_by_priority").to_string()];
+    for (priority, entry) in PRIORITIES.iter().zip(&stats.open_by_priority) {
+        lines.push(format!("  {:<12}{:>5}", priority.to_string(), entry.count));
+    }
+    lines.push(format!("  {:<12}{:>5}", t("stats_total"), stats.open_total));
+    lines.push(String::new());
+
+    lines.push(format!("{:<23} {:>5}", t("stats_overdue"), stats.overdue));
+    let age = match stats.average_open_age_days {
+        Some(days) => tf("stats_days", &[("days", &format!("{days:.1}"))]),
+        None => t("stats_not_available").to_string(),
+    };
+    lines.push(format!("{:<23} {:>5}", t("stats_average_age"), age));
+    lines.push(String::new());
+
+    lines.push(t("stats_completed_per_week").to_string());
+    for week in &stats.completed_per_week {
+        let label = tf("stats_week_of", &[("date", &week.week_start)]);
+        lines.push(format!("  {label}{:>5}", week.count));
+    }
+    lines.push(String::new());
+
+    lines.push(t("stats_timeliness").to_string());
+    lines.push(format!(
+        "  {:<12}{:>5}",
+        t("stats_on_time"),
+        stats.completed_on_time
+    ));
+    lines.push(format!(
+        "  {:<12}{:>5}",
+        t("stats_late"),
+        stats.completed_late
+    ));
+    let rate = match stats.on_time_rate {
+        Some(rate) => format!("{:.0}%", rate * 100.0),
+        None => t("stats_not_available").to_string(),
+    };
+    lines.push(format!("  {:<12}{:>5}", t("stats_on_time_rate"), rate));
+
+    lines.join("\n")
+}
+
+// One `section<TAB>key<TAB>value` row per figure, for the tsv format.
+pub fn render_tsv(stats: &Stats) -> String {
+    let mut rows = Vec::new();
+    for entry in &stats.open_by_priority {
+        rows.push(format!(
+            "open_by_priority\t{}\t{}",
+            entry.priority, entry.count
+        ));
+    }
+    rows.push(format!("open\ttotal\t{}", stats.open_total));
+    rows.push(format!("open\toverdue\t{}", stats.overdue));
+    rows.push(format!(
+        "open\taverage_age_days\t{}",
+        stats
+            .average_open_age_days
+            .map(|d| format!("{d:.2}"))
+            .unwrap_or_default()
+    ));
+    for week in &stats.completed_per_week {
+        rows.push(format!(
+            "completed_per_week\t{}\t{}",
+            week.week_start, week.count
+        ));
+    }
+    rows.push(format!("completed\ton_time\t{}", stats.completed_on_time));
+    rows.push(format!("completed\tlate\t{}", stats.completed_late));
+    rows.push(format!(
+        "completed\ton_time_rate\t{}",
+        stats
+            .on_time_rate
+            .map(|r| format!("{r:.4}"))
+            .unwrap_or_default()
+    ));
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Status;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+    }
+
+    fn task(priority: Priority, due_date: &str, history: &[&str]) -> Task {
+        Task {
+            id: "1A".to_string(),
+            description: "Task".to_string(),
+            due_date: due_date.to_string(),
+            priority,
+            history: history.iter().map(|h| h.parse().unwrap()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn done(due_date: &str, completed: &str) -> Task {
+        let mut task = task(
+            Priority::None,
+            due_date,
+            &["2026-09-01T08:00:00 created", completed],
+        );
+        task.status = Status::Done;
+        task
+    }
+
+    #[test]
+    fn open_task_figures() {
+        let open = vec![
+            task(
+                Priority::Asap,
+                "10/20/2026",
+                &["2026-10-11T08:00:00 created"],
+            ),
+            task(
+                Priority::Asap,
+                "10/25/2026",
+                &["2026-10-17T08:00:00 created"],
+            ),
+            task(Priority::Minor, "", &[]),
+        ];
+        let stats = compute(&open, &[], today(), 4);
+        assert_eq!(stats.open_total, 3);
+        assert_eq!(stats.open_by_priority[0].count, 2);
+        assert_eq!(stats.open_by_priority[3].count, 1);
+        assert_eq!(stats.overdue, 1);
+        assert_eq!(stats.average_open_age_days, Some(7.0));
+    }
+
+    #[test]
+    fn completion_figures() {
+        let archive = vec![
+            done("10/20/2026", "2026-10-19T12:00:00 completed"),
+            done("10/20/2026", "2026-10-20T23:00:00 completed"),
+            done("10/10/2026", "2026-10-14T09:00:00 completed"),
+            done("", "2026-10-01T09:00:00 completed"),
+        ];
+        let stats = compute(&[], &archive, today(), 4);
+        assert_eq!(
+            stats.completed_per_week,
+            vec![
+                WeekCount {
+                    week_start: "2026-09-28".to_string(),
+                    count: 1
+                },
+                WeekCount {
+                    week_start: "2026-10-05".to_string(),
+                    count: 0
+                },
+                WeekCount {
+                    week_start: "2026-10-12".to_string(),
+                    count: 1
+                },
+                WeekCount {
+                    week_start: "2026-10-19".to_string(),
+                    count: 2
+                },
+            ]
+        );
+        assert_eq!(stats.completed_on_time, 2);
+        assert_eq!(stats.completed_late, 1);
+        assert!((stats.on_time_rate.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_database_has_no_rates() {
+        let stats = compute(&[], &[], today(), 2);
+        assert_eq!(stats.average_open_age_days, None);
+        assert_eq!(stats.on_time_rate, None);
+        assert!(render_text(&stats).contains("n/a"));
+    }
+}
@@ -1,44 +1,318 @@
-use crate::models::Task;
+use crate::hooks::{Event, Hooks};
+use crate::i18n::{t, tf};
+use crate::models::{Action, Status, Task};
+use crate::rank::{self, Place};
 use crate::xml_parser::write as write_to_xml;
+use chrono::NaiveDateTime;
 use std::collections::HashSet;
 use std::error::Error;
-
+use std::str::FromStr;
+
+// Open tasks live in `tasks`; completed tasks are kept in `archive` so their
+// history stays available for reporting, and deleted tasks sit in `trash`
+// until they are restored or the trash is emptied. Only open tasks hold on to
+// an ID. Every change to a task goes through `commit`, so hooks see all of
+// them. The one exception is `empty_trash`, which drops tasks rather than
+// changing them.
 pub struct TaskManager {
     tasks: Vec<Task>,
+    archive: Vec<Task>,
+    trash: Vec<Task>,
+    id_strategy: IdStrategy,
+    hooks: Hooks,
+}
+
+// How new task IDs are chosen: a random free ID, or the lowest free one.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum IdStrategy {
+    #[default]
+    Random,
+    Sequential,
+}
+
+impl FromStr for IdStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "random" => Ok(IdStrategy::Random),
+            "sequential" => Ok(IdStrategy::Sequential),
+            other => Err(tf("unknown_id_strategy", &[("strategy", &other)])),
+        }
+    }
 }
 
 impl TaskManager {
     pub fn new() -> Self {
-        TaskManager { tasks: Vec::new() }
+        TaskManager {
+            tasks: Vec::new(),
+            archive: Vec::new(),
+            trash: Vec::new(),
+            id_strategy: IdStrategy::default(),
+            hooks: Hooks::default(),
+        }
+    }
+
+    pub fn set_id_strategy(&mut self, strategy: IdStrategy) {
+        self.id_strategy = strategy;
+    }
+
+    pub fn set_hooks(&mut self, hooks: Hooks) {
+        self.hooks = hooks;
+    }
+
+    fn next_id(&self) -> Result<String, String> {
+        let existing: Vec<&str> = self.tasks.iter().map(|t| t.id.as_str()).collect();
+        match self.id_strategy {
+            IdStrategy::Random => generate_unique_id(&existing),
+            IdStrategy::Sequential => generate_sequential_id(&existing),
+        }
     }
 
     pub fn set_tasks(&mut self, new_tasks: Vec<Task>) {
-        self.tasks = new_tasks;
+        self.tasks.clear();
+        self.archive.clear();
+        self.trash.clear();
+        for task in new_tasks {
+            match task.status {
+                Status::Open => self.tasks.push(task),
+                Status::Done => self.archive.push(task),
+                Status::Deleted => self.trash.push(task),
+            }
+        }
     }
 
     pub fn fetch_tasks(&self) -> &[Task] {
         &self.tasks
     }
 
-    pub fn add_task(&mut self, task: Task) {
-        self.tasks.push(task);
+    pub fn fetch_archive(&self) -> &[Task] {
+        &self.archive
+    }
+
+    pub fn fetch_trash(&self) -> &[Task] {
+        &self.trash
+    }
+
+    fn list_mut(&mut self, status: Status) -> &mut Vec<Task> {
+        match status {
+            Status::Open => &mut self.tasks,
+            Status::Done => &mut self.archive,
+            Status::Deleted => &mut self.trash,
+        }
+    }
+
+    // Runs the hooks for `event` and, unless one of them vetoes it, stores
+    // the task they hand back in place of `before`. A task that stays in the
+    // same list keeps its place there.
+    fn commit(&mut self, event: Event, before: Option<Task>, after: Task) -> Result<&Task, String> {
+        let after = self.hooks.run(event, before.as_ref(), after)?;
+        let status = after.status;
+        let mut index = None;
+        if let Some(before) = before {
+            let list = self.list_mut(before.status);
+            if let Some(pos) = list.iter().rposition(|t| t.id == before.id) {
+                list.remove(pos);
+                if before.status == status {
+                    index = Some(pos);
+                }
+            }
+        }
+        let list = self.list_mut(status);
+        let index = index.unwrap_or(list.len());
+        list.insert(index, after);
+        Ok(&list[index])
+    }
+
+    // A rank after every open task.
+    fn bottom_rank(&self) -> String {
+        let last = self.tasks.iter().map(|t| t.rank.as_str()).max();
+        rank::between(last.filter(|r| !r.is_empty()), None)
+    }
+
+    // Files from before manual ordering have no ranks. Open tasks without one
+    // are ranked after the others, in file order. Returns whether any task
+    // was given a rank.
+    pub fn rank_unranked(&mut self) -> bool {
+        let unranked = self.tasks.iter().filter(|t| t.rank.is_empty()).count();
+        if unranked == 0 {
+            return false;
+        }
+        let mut ranks = if unranked == self.tasks.len() {
+            rank::spread(unranked)
+        } else {
+            let mut last = self.bottom_rank();
+            let mut ranks = Vec::new();
+            for _ in 0..unranked {
+                let next = rank::between(Some(&last), None);
+                ranks.push(std::mem::replace(&mut last, next));
+            }
+            ranks
+        }
+        .into_iter();
+        for task in self.tasks.iter_mut().filter(|t| t.rank.is_empty()) {
+            task.rank = ranks.next().unwrap_or_default();
+        }
+        true
+    }
+
+    // Gives an open task a rank that puts it at `place` in the manual order.
+    // Only this task's rank changes, and not at all if it is already there.
+    pub fn move_task(&mut self, id: &str, place: &Place) -> Result<&Task, String> {
+        let mut task = self
+            .find_task_by_id(id)
+            .cloned()
+            .ok_or_else(|| tf("task_not_found", &[("id", &id)]))?;
+        let mut others: Vec<&str> = self
+            .tasks
+            .iter()
+            .filter(|t| t.id != id)
+            .map(|t| t.rank.as_str())
+            .collect();
+        others.sort();
+        let neighbour = |other: &str| {
+            let rank = self
+                .find_task_by_id(other)
+                .filter(|t| t.id != id)
+                .map(|t| t.rank.as_str())
+                .ok_or_else(|| tf("task_not_found", &[("id", &other)]))?;
+            Ok::<usize, String>(others.partition_point(|r| *r < rank))
+        };
+        let (low, high) = match place {
+            Place::Top => (None, others.first().copied()),
+            Place::Bottom => (others.last().copied(), None),
+            Place::Before(other) => {
+                let pos = neighbour(other)?;
+                (pos.checked_sub(1).map(|p| others[p]), Some(others[pos]))
+            }
+            Place::After(other) => {
+                let pos = neighbour(other)?;
+                (Some(others[pos]), others.get(pos + 1).copied())
+            }
+        };
+        let fits = !task.rank.is_empty()
+            && low.is_none_or(|low| low < task.rank.as_str())
+            && high.is_none_or(|high| task.rank.as_str() < high);
+        if !fits {
+            task.rank = rank::between(low.filter(|r| !r.is_empty()), high);
+            task.record(Action::Edited, "rank");
+        }
+        self.update_task(task)
+    }
+
+    // Gives a new task a free ID and the last place in the manual order,
+    // records its creation and adds it.
+    pub fn create_task(&mut self, mut task: Task) -> Result<&Task, String> {
+        task.id = self.next_id()?;
+        task.rank = self.bottom_rank();
+        task.record(Action::Created, "");
+        self.commit(Event::Add, None, task)
+    }
+
+    // Replaces the open task with the same ID. Unchanged tasks are left alone
+    // without running any hooks.
+    pub fn update_task(&mut self, task: Task) -> Result<&Task, String> {
+        let before = self
+            .find_task_by_id(&task.id)
+            .cloned()
+            .ok_or_else(|| tf("task_not_found", &[("id", &task.id)]))?;
+        if before == task {
+            return Ok(self.find_task_by_id(&before.id).unwrap());
+        }
+        self.commit(Event::Modify, Some(before), task)
     }
 
     pub fn save_tasks(&self, filename: &str) -> Result<(), Box<dyn Error>> {
-        write_to_xml(filename, &self.tasks)
+        let all: Vec<Task> = self
+            .tasks
+            .iter()
+            .chain(&self.archive)
+            .chain(&self.trash)
+            .cloned()
+            .collect();
+        write_to_xml(filename, &all)
     }
 
     pub fn find_task_by_id(&self, id: &str) -> Option<&Task> {
         self.tasks.iter().find(|t| t.id == id)
     }
 
-    pub fn find_task_by_id_mut(&mut self, id: &str) -> Option<&mut Task> {
-        self.tasks.iter_mut().find(|t| t.id == id)
+    // Also looks in the archive and the trash, newest first, since an ID can
+    // be reused once its task is completed or deleted.
+    pub fn find_any_task_by_id(&self, id: &str) -> Option<&Task> {
+        self.tasks
+            .iter()
+            .chain(self.archive.iter().rev())
+            .chain(self.trash.iter().rev())
+            .find(|t| t.id == id)
+    }
+
+    // Moves an open task to `status`. Returns `None` when there is no open
+    // task with this ID, and an error when a hook vetoes the change.
+    fn close_task(
+        &mut self,
+        id: &str,
+        status: Status,
+        action: Action,
+        event: Event,
+    ) -> Result<Option<&Task>, String> {
+        let Some(before) = self.find_task_by_id(id).cloned() else {
+            return Ok(None);
+        };
+        let mut task = before.clone();
+        task.status = status;
+        task.record(action, "");
+        self.commit(event, Some(before), task).map(Some)
+    }
+
+    pub fn complete_task_by_id(&mut self, id: &str) -> Result<Option<&Task>, String> {
+        self.close_task(id, Status::Done, Action::Completed, Event::Complete)
+    }
+
+    pub fn delete_task_by_id(&mut self, id: &str) -> Result<Option<&Task>, String> {
+        self.close_task(id, Status::Deleted, Action::Deleted, Event::Delete)
     }
 
-    pub fn remove_task_by_id(&mut self, id: &str) -> Option<Task> {
-        self.tasks.iter().position(|t| t.id == id)
-            .map(|pos| self.tasks.remove(pos))
+    // Restores the most recently deleted task with this ID. The task keeps its
+    // original ID unless an open task has taken it in the meantime, in which
+    // case it gets a fresh one.
+    pub fn restore_task_by_id(&mut self, id: &str) -> Result<&Task, String> {
+        let pos = self
+            .trash
+            .iter()
+            .rposition(|t| t.id == id)
+            .ok_or_else(|| tf("not_in_trash", &[("id", &id)]))?;
+
+        let before = self.trash[pos].clone();
+        let mut task = before.clone();
+        if self.find_task_by_id(&task.id).is_some() {
+            let new_id = self.next_id()?;
+            task.record(Action::Restored, format!("{} as {new_id}", task.id));
+            task.id = new_id;
+        } else {
+            task.record(Action::Restored, "");
+        }
+        // A restored task goes back to its old place unless that is taken.
+        if task.rank.is_empty() || self.tasks.iter().any(|t| t.rank == task.rank) {
+            task.rank = self.bottom_rank();
+        }
+        task.status = Status::Open;
+        self.commit(Event::Modify, Some(before), task)
+    }
+
+    // Permanently drops trashed tasks deleted before `cutoff`, or all of them
+    // when no cutoff is given. Returns how many were removed. This bypasses
+    // `commit`: hooks are handed the task after a change, and a purged task
+    // has none. Its `on-delete` hooks already ran when it was trashed.
+    pub fn empty_trash(&mut self, cutoff: Option<NaiveDateTime>) -> usize {
+        let before = self.trash.len();
+        self.trash.retain(|task| match (cutoff, task.deleted_at()) {
+            (None, _) => false,
+            (Some(cutoff), Some(deleted)) => deleted >= cutoff,
+            // Without a recorded deletion time we cannot tell its age.
+            (Some(_), None) => true,
+        });
+        before - self.trash.len()
     }
 }
 
@@ -48,6 +322,17 @@ impl Default for TaskManager {
     }
 }
 
+pub fn generate_sequential_id(existing_ids: &[&str]) -> Result<String, String> {
+    let used: HashSet<u8> = existing_ids
+        .iter()
+        .filter_map(|id| u8::from_str_radix(id, 16).ok())
+        .collect();
+    (0u8..=255)
+        .find(|n| !used.contains(n))
+        .map(|n| format!("{:02X}", n))
+        .ok_or_else(|| t("ids_exhausted").to_string())
+}
+
 pub fn generate_unique_id(existing_ids: &[&str]) -> Result<String, String> {
     use rand::seq::SliceRandom;
 
@@ -59,7 +344,7 @@ pub fn generate_unique_id(existing_ids: &[&str]) -> Result<String, String> {
     let available: Vec<u8> = (0u8..=255).filter(|n| !used.contains(n)).collect();
 
     if available.is_empty() {
-        return Err("All 256 task IDs are in use. Cannot create a new task.".to_string());
+        return Err(t("ids_exhausted").to_string());
     }
 
     let mut rng = rand::thread_rng();
@@ -80,6 +365,7 @@ mod tests {
                 due_date: "1/25/2023".to_string(),
                 priority: Priority::None,
                 notes: "".to_string(),
+                ..Default::default()
             },
             Task {
                 id: "2B".to_string(),
@@ -87,6 +373,7 @@ mod tests {
                 due_date: "3/10/2023".to_string(),
                 priority: Priority::None,
                 notes: "".to_string(),
+                ..Default::default()
             },
             Task {
                 id: "3C".to_string(),
@@ -94,6 +381,7 @@ mod tests {
                 due_date: "5/31/2023".to_string(),
                 priority: Priority::None,
                 notes: "".to_string(),
+                ..Default::default()
             },
         ]
     }
@@ -118,39 +406,228 @@ mod tests {
         manager.set_tasks(sample_tasks());
 
         let new_task = Task {
-            id: "FF".to_string(),
             description: "New task".to_string(),
             due_date: "6/20/2023".to_string(),
             priority: Priority::None,
             notes: "".to_string(),
+            ..Default::default()
         };
-        manager.add_task(new_task.clone());
+        let created = manager.create_task(new_task).unwrap().clone();
 
         assert_eq!(manager.fetch_tasks().len(), 4);
-        assert_eq!(manager.fetch_tasks().last().unwrap(), &new_task);
+        assert_eq!(manager.fetch_tasks().last().unwrap(), &created);
+        assert_eq!(created.description, "New task");
+        assert_eq!(created.due_date, "6/20/2023");
+    }
+
+    #[test]
+    fn test_create_task_assigns_free_id() {
+        let mut manager = TaskManager::new();
+        manager.set_tasks(sample_tasks());
+        let existing: Vec<String> = manager.fetch_tasks().iter().map(|t| t.id.clone()).collect();
+
+        let created = manager
+            .create_task(Task {
+                description: "New task".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert!(!existing.contains(&created.id));
+        assert!(created.created_at().is_some());
+        assert_eq!(manager.fetch_tasks().len(), 4);
+    }
+
+    #[test]
+    fn test_update_task_keeps_position() {
+        let mut manager = TaskManager::new();
+        manager.set_tasks(sample_tasks());
+
+        let mut task = manager.find_task_by_id("2B").unwrap().clone();
+        task.description = "Renamed".to_string();
+        manager.update_task(task).unwrap();
+        assert_eq!(manager.fetch_tasks()[1].description, "Renamed");
+
+        let missing = Task {
+            id: "ZZ".to_string(),
+            ..Default::default()
+        };
+        assert!(manager.update_task(missing).is_err());
+    }
+
+    #[test]
+    fn test_move_task_changes_only_its_rank() {
+        let mut manager = TaskManager::new();
+        manager.set_tasks(sample_tasks());
+        assert!(manager.rank_unranked());
+        assert!(!manager.rank_unranked());
+        let ranks = |manager: &TaskManager| -> Vec<String> {
+            manager
+                .fetch_tasks()
+                .iter()
+                .map(|t| t.rank.clone())
+                .collect()
+        };
+        let order = |manager: &TaskManager| -> Vec<String> {
+            let mut tasks: Vec<&Task> = manager.fetch_tasks().iter().collect();
+            tasks.sort_by(|a, b| a.rank.cmp(&b.rank));
+            tasks.iter().map(|t| t.id.clone()).collect()
+        };
+        assert_eq!(order(&manager), vec!["1A", "2B", "3C"]);
+        let before = ranks(&manager);
+
+        manager
+            .move_task("3C", &Place::Before("2B".to_string()))
+            .unwrap();
+        assert_eq!(order(&manager), vec!["1A", "3C", "2B"]);
+        assert_eq!(ranks(&manager)[..2], before[..2]);
+
+        manager.move_task("1A", &Place::Bottom).unwrap();
+        manager.move_task("2B", &Place::Top).unwrap();
+        assert_eq!(order(&manager), vec!["2B", "3C", "1A"]);
+        manager
+            .move_task("2B", &Place::After("3C".to_string()))
+            .unwrap();
+        assert_eq!(order(&manager), vec!["3C", "2B", "1A"]);
+
+        let history = manager.find_task_by_id("3C").unwrap().history.len();
+        manager.move_task("3C", &Place::Top).unwrap();
+        assert_eq!(
+            manager.find_task_by_id("3C").unwrap().history.len(),
+            history
+        );
+        assert!(manager
+            .move_task("3C", &Place::Before("3C".to_string()))
+            .is_err());
+
+        // A deleted and restored task goes back to its old place.
+        manager.delete_task_by_id("2B").unwrap();
+        let created = manager
+            .create_task(Task {
+                description: "New task".to_string(),
+                ..Default::default()
+            })
+            .unwrap()
+            .id
+            .clone();
+        manager.restore_task_by_id("2B").unwrap();
+        assert_eq!(
+            order(&manager),
+            vec!["3C".to_string(), "2B".into(), "1A".into(), created]
+        );
     }
 
     #[test]
-    fn test_remove_task_by_id_valid() {
+    fn test_delete_task_by_id_valid() {
         let mut manager = TaskManager::new();
         manager.set_tasks(sample_tasks());
 
-        let removed = manager.remove_task_by_id("2B");
+        let removed = manager.delete_task_by_id("2B").unwrap();
         assert!(removed.is_some());
         assert_eq!(removed.unwrap().description, "Example task two");
         assert_eq!(manager.fetch_tasks().len(), 2);
     }
 
     #[test]
-    fn test_remove_task_by_id_invalid() {
+    fn test_delete_task_by_id_invalid() {
         let mut manager = TaskManager::new();
         manager.set_tasks(sample_tasks());
 
-        let removed = manager.remove_task_by_id("ZZ");
+        let removed = manager.delete_task_by_id("ZZ").unwrap();
         assert!(removed.is_none());
         assert_eq!(manager.fetch_tasks().len(), 3);
     }
 
+    #[test]
+    fn test_complete_task_moves_to_archive() {
+        let mut manager = TaskManager::new();
+        manager.set_tasks(sample_tasks());
+
+        let completed = manager.complete_task_by_id("2B").unwrap().unwrap();
+        assert_eq!(completed.status, Status::Done);
+        assert!(completed.completed_at().is_some());
+
+        assert_eq!(manager.fetch_tasks().len(), 2);
+        assert!(manager.find_task_by_id("2B").is_none());
+        assert_eq!(manager.fetch_archive().len(), 1);
+        assert!(manager.complete_task_by_id("2B").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_delete_and_restore_keeps_id() {
+        let mut manager = TaskManager::new();
+        manager.set_tasks(sample_tasks());
+
+        manager.delete_task_by_id("2B").unwrap().unwrap();
+        assert!(manager.find_task_by_id("2B").is_none());
+        assert_eq!(manager.fetch_trash().len(), 1);
+        assert!(manager.fetch_trash()[0].deleted_at().is_some());
+
+        let restored = manager.restore_task_by_id("2B").unwrap();
+        assert_eq!(restored.id, "2B");
+        assert_eq!(restored.status, Status::Open);
+        assert!(manager.fetch_trash().is_empty());
+        assert_eq!(manager.fetch_tasks().len(), 3);
+    }
+
+    #[test]
+    fn test_restore_reissues_reused_id() {
+        let mut tasks = sample_tasks();
+        tasks[1].status = Status::Deleted;
+        let mut reused = tasks[0].clone();
+        reused.id = "2B".to_string();
+        tasks.push(reused);
+        let mut manager = TaskManager::new();
+        manager.set_tasks(tasks);
+
+        let restored = manager.restore_task_by_id("2B").unwrap();
+        assert_ne!(restored.id, "2B");
+        assert_eq!(restored.description, "Example task two");
+        assert!(restored
+            .history
+            .last()
+            .unwrap()
+            .detail
+            .starts_with("2B as "));
+    }
+
+    #[test]
+    fn test_restore_missing_id() {
+        let mut manager = TaskManager::new();
+        manager.set_tasks(sample_tasks());
+        assert!(manager.restore_task_by_id("1A").is_err());
+    }
+
+    #[test]
+    fn test_empty_trash_older_than() {
+        let mut tasks = sample_tasks();
+        for (task, when) in tasks.iter_mut().zip(["2026-08-01", "2026-10-01", ""]) {
+            task.status = Status::Deleted;
+            if !when.is_empty() {
+                task.history = vec![format!("{when}T00:00:00 deleted").parse().unwrap()];
+            }
+        }
+        let mut manager = TaskManager::new();
+        manager.set_tasks(tasks);
+
+        let cutoff = NaiveDateTime::parse_from_str("2026-09-01T00:00:00", "%Y-%m-%dT%H:%M:%S").ok();
+        assert_eq!(manager.empty_trash(cutoff), 1);
+        assert_eq!(manager.fetch_trash().len(), 2);
+        assert_eq!(manager.empty_trash(None), 2);
+        assert!(manager.fetch_trash().is_empty());
+    }
+
+    #[test]
+    fn test_set_tasks_partitions_by_status() {
+        let mut tasks = sample_tasks();
+        tasks[0].status = Status::Done;
+        let mut manager = TaskManager::new();
+        manager.set_tasks(tasks);
+
+        assert_eq!(manager.fetch_tasks().len(), 2);
+        assert_eq!(manager.fetch_archive()[0].id, "1A");
+    }
+
     #[test]
     fn test_find_task_by_id() {
         let mut manager = TaskManager::new();
@@ -186,4 +663,18 @@ mod tests {
         let result = generate_unique_id(&all_ids_refs);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_sequential_ids_fill_the_lowest_gap() {
+        assert_eq!(generate_sequential_id(&[]), Ok("00".to_string()));
+        assert_eq!(
+            generate_sequential_id(&["00", "01", "03"]),
+            Ok("02".to_string())
+        );
+
+        let mut manager = TaskManager::new();
+        manager.set_id_strategy("sequential".parse().unwrap());
+        let created = manager.create_task(Task::default()).unwrap();
+        assert_eq!(created.id, "00");
+    }
 }
@@ -0,0 +1,360 @@
+use crate::dates::{format_stored_date, DatePattern};
+use crate::i18n::{create_error, read_error, remove_error, t, tf, write_error};
+use crate::models::{Priority, Task};
+use crate::snooze::DueChange;
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{value, Array, DocumentMut, Item};
+
+// A stored task shape, one `NAME.toml` file per template. `{placeholders}` in
+// the description and notes are asked for when a task is created from it,
+// and `due` is an offset such as `+3d` from the day it is used.
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct Template {
+    pub name: String,
+    pub description: String,
+    pub priority: String,
+    pub due: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+}
+
+fn check_name(name: &str) -> Result<(), String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(tf("invalid_template_name", &[("name", &name)]))
+    }
+}
+
+fn parse_offset(input: &str) -> Result<DueChange, String> {
+    // The day does not matter for offsets, only for dates, which are refused.
+    let today = NaiveDate::default();
+    DueChange::parse(input, &DatePattern::default(), today)
+        .ok()
+        .filter(|change| change.is_offset())
+        .ok_or_else(|| tf("invalid_due_offset", &[("input", &input)]))
+}
+
+// The placeholder names in `text`, in order of first appearance.
+fn placeholders_in(text: &str) -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let end = after
+            .find(['{', '}'])
+            .filter(|&end| after[end..].starts_with('}'))
+            .ok_or_else(|| tf("placeholder_unclosed", &[("text", &text)]))?;
+        let name = &after[..end];
+        if name.trim().is_empty() {
+            return Err(tf("placeholder_empty", &[("text", &text)]));
+        }
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &after[end + 1..];
+    }
+    if rest.contains('}') {
+        return Err(tf("placeholder_unmatched", &[("text", &text)]));
+    }
+    Ok(names)
+}
+
+// Replaces each placeholder in one pass, so values are never expanded again.
+fn fill(text: &str, values: &[(String, String)]) -> String {
+    let mut filled = String::new();
+    let mut rest = text;
+    while let Some((start, end)) = rest
+        .find('{')
+        .and_then(|start| Some((start, start + rest[start..].find('}')?)))
+    {
+        filled.push_str(&rest[..start]);
+        match values
+            .iter()
+            .find(|(name, _)| *name == rest[start + 1..end])
+        {
+            Some((_, value)) => filled.push_str(value),
+            None => filled.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    filled.push_str(rest);
+    filled
+}
+
+impl Template {
+    // Saves a task's shape. Its due date becomes an offset from the day the
+    // task was created, so a template made from a task due a week after it
+    // was added is due a week after each use.
+    pub fn from_task(name: &str, task: &Task, today: NaiveDate) -> Result<Template, String> {
+        check_name(name)?;
+        let start = task.created_at().map(|at| at.date()).unwrap_or(today);
+        let due = match task.due() {
+            Some(due) if due >= start => format!("+{}d", (due - start).num_days()),
+            _ => String::new(),
+        };
+        let template = Template {
+            name: name.to_string(),
+            description: task.description.clone(),
+            priority: task.priority.name().to_lowercase(),
+            due,
+            notes: task.notes.clone(),
+            tags: task.tags.clone(),
+        };
+        template.placeholders()?;
+        Ok(template)
+    }
+
+    // Reads and validates a template file's contents. Unknown keys are
+    // refused so that typos do not go unnoticed.
+    pub fn parse(name: &str, text: &str) -> Result<Template, String> {
+        check_name(name)?;
+        let document = text.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+        let mut template = Template {
+            name: name.to_string(),
+            description: String::new(),
+            priority: "none".to_string(),
+            due: String::new(),
+            notes: String::new(),
+            tags: Vec::new(),
+        };
+        for (key, item) in document.iter() {
+            let text = || {
+                item.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| tf("value_not_string", &[("field", &key)]))
+            };
+            match key {
+                "description" => template.description = text()?,
+                "priority" => template.priority = text()?,
+                "due" => template.due = text()?,
+                "notes" => template.notes = text()?,
+                "tags" => {
+                    template.tags = item
+                        .as_array()
+                        .and_then(|tags| {
+                            tags.iter()
+                                .map(|tag| tag.as_str().map(str::to_string))
+                                .collect()
+                        })
+                        .ok_or_else(|| t("template_tags_not_strings").to_string())?
+                }
+                other => return Err(tf("unknown_field", &[("field", &other)])),
+            }
+        }
+
+        if template.description.trim().is_empty() {
+            return Err(t("template_no_description").to_string());
+        }
+        Priority::from_name(&template.priority)
+            .ok_or_else(|| tf("invalid_priority", &[("value", &template.priority)]))?;
+        if !template.due.is_empty() {
+            parse_offset(&template.due)?;
+        }
+        template.placeholders()?;
+        Ok(template)
+    }
+
+    pub fn set_due(&mut self, offset: &str) -> Result<(), String> {
+        parse_offset(offset)?;
+        self.due = offset.to_string();
+        Ok(())
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut document = DocumentMut::new();
+        document["description"] = value(&self.description);
+        document["priority"] = value(&self.priority);
+        if !self.due.is_empty() {
+            document["due"] = value(&self.due);
+        }
+        if !self.notes.is_empty() {
+            document["notes"] = value(&self.notes);
+        }
+        if !self.tags.is_empty() {
+            document["tags"] = Item::Value(self.tags.iter().collect::<Array>().into());
+        }
+        document.to_string()
+    }
+
+    pub fn placeholders(&self) -> Result<Vec<String>, String> {
+        let mut names = placeholders_in(&self.description)?;
+        for name in placeholders_in(&self.notes)? {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        Ok(names)
+    }
+
+    // A new task with the placeholders filled in and the due date counted
+    // from `today`.
+    pub fn instantiate(&self, values: &[(String, String)], today: NaiveDate) -> Task {
+        let due_date = parse_offset(&self.due)
+            .ok()
+            .and_then(|change| change.apply(today))
+            .map(format_stored_date)
+            .unwrap_or_default();
+        Task {
+            description: fill(&self.description, values),
+            due_date,
+            priority: Priority::from_name(&self.priority).unwrap_or_default(),
+            notes: fill(&self.notes, values),
+            tags: self.tags.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+fn path(dir: &Path, name: &str) -> Result<PathBuf, String> {
+    check_name(name)?;
+    Ok(dir.join(format!("{name}.toml")))
+}
+
+pub fn load(dir: &Path, name: &str) -> Result<Template, String> {
+    let path = path(dir, name)?;
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(tf("no_template", &[("name", &name)]))
+        }
+        Err(e) => return Err(read_error(&path, e)),
+    };
+    Template::parse(name, &text).map_err(|e| {
+        tf(
+            "invalid_template",
+            &[("file", &path.display()), ("error", &e)],
+        )
+    })
+}
+
+// Every template in `dir`, sorted by name. A missing directory has none.
+pub fn list(dir: &Path) -> Result<Vec<Template>, String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_suffix(".toml")
+                .map(str::to_string)
+        })
+        .collect();
+    names.sort();
+    names.iter().map(|name| load(dir, name)).collect()
+}
+
+// Refuses to replace an existing template unless `replace` is set.
+pub fn save(dir: &Path, template: &Template, replace: bool) -> Result<PathBuf, String> {
+    let path = path(dir, &template.name)?;
+    if path.exists() && !replace {
+        return Err(tf("template_exists", &[("name", &template.name)]));
+    }
+    fs::create_dir_all(dir).map_err(|e| create_error(dir, e))?;
+    fs::write(&path, template.to_toml()).map_err(|e| write_error(&path, e))?;
+    Ok(path)
+}
+
+pub fn delete(dir: &Path, name: &str) -> Result<(), String> {
+    let path = path(dir, name)?;
+    fs::remove_file(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => tf("no_template", &[("name", &name)]),
+        _ => remove_error(&path, e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+    }
+
+    const RELEASE: &str = r#"
+description = "Release {version} of {project}"
+priority = "important"
+due = "+1w"
+notes = "Announce {version} when done"
+tags = ["release"]
+"#;
+
+    #[test]
+    fn placeholders_are_found_in_order() {
+        assert_eq!(
+            placeholders_in("Ship {a} and {b} after {a}"),
+            Ok(vec!["a".to_string(), "b".to_string()])
+        );
+        assert!(placeholders_in("Ship {a").is_err());
+        assert!(placeholders_in("Ship {a{b}}").is_err());
+        assert!(placeholders_in("Ship a}").is_err());
+        assert!(placeholders_in("Ship {}").is_err());
+    }
+
+    #[test]
+    fn instantiates_with_values_and_offset() {
+        let template = Template::parse("release", RELEASE).unwrap();
+        assert_eq!(template.placeholders().unwrap(), vec!["version", "project"]);
+
+        let values = vec![
+            ("version".to_string(), "2.1".to_string()),
+            ("project".to_string(), "todo_cli {version}".to_string()),
+        ];
+        let task = template.instantiate(&values, today());
+        assert_eq!(task.description, "Release 2.1 of todo_cli {version}");
+        assert_eq!(task.notes, "Announce 2.1 when done");
+        assert_eq!(task.due_date, "2026-10-28");
+        assert_eq!(task.priority, Priority::Important);
+        assert_eq!(task.tags, vec!["release"]);
+    }
+
+    #[test]
+    fn invalid_templates_name_the_problem() {
+        let invalid = |text: &str| Template::parse("t", text).unwrap_err();
+        assert_eq!(invalid("priority = \"high\""), "'description' is missing.");
+        assert!(invalid("description = \"x\"\npriority = \"high\"").contains("priority"));
+        assert!(invalid("description = \"x\"\ndue = \"friday\"").contains("due offset"));
+        assert!(invalid("description = \"x\"\ndue = \"+\"").contains("due offset"));
+        assert!(invalid("description = \"x\"\ndue = \"+200000000000d\"").contains("due offset"));
+        assert!(invalid("description = \"x\"\ncolor = \"red\"").contains("color"));
+        assert!(invalid("description = \"x {\"").contains("Unclosed"));
+        assert!(Template::parse("../t", "description = \"x\"").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_the_file() {
+        let dir = std::env::temp_dir().join(format!("todo_cli_templates_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut task = Task {
+            description: "Review {topic}".to_string(),
+            due_date: "2026-10-24".to_string(),
+            priority: Priority::Medium,
+            tags: vec!["review".to_string()],
+            ..Default::default()
+        };
+        task.history
+            .push("2026-10-21T09:00:00 created".parse().unwrap());
+        let template = Template::from_task("review", &task, today()).unwrap();
+        assert_eq!(template.due, "+3d");
+
+        save(&dir, &template, false).unwrap();
+        assert!(save(&dir, &template, false).is_err());
+        assert_eq!(list(&dir).unwrap(), vec![template.clone()]);
+        assert_eq!(load(&dir, "review").unwrap(), template);
+
+        delete(&dir, "review").unwrap();
+        assert!(load(&dir, "review").is_err());
+        assert!(list(&dir).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,690 @@
+use crate::bulk::{apply_updates, FieldUpdate};
+use crate::config::Config;
+use crate::dates::DatePattern;
+use crate::editor;
+use crate::filter::{sort_tasks, Filter, SortKey};
+use crate::i18n::{t, tf};
+use crate::models::{Priority, Task};
+use crate::render::Renderer;
+use crate::task_manager::TaskManager;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io::IsTerminal;
+
+const PAGE: usize = 10;
+
+// What the event loop should do after a key press. Opening the editor needs
+// the real terminal, so the app only asks for it.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Continue,
+    Quit,
+    OpenEditor(Option<String>),
+}
+
+fn next_priority(priority: &Priority) -> Priority {
+    match priority {
+        Priority::Asap => Priority::Important,
+        Priority::Important => Priority::Medium,
+        Priority::Medium => Priority::Minor,
+        Priority::Minor => Priority::None,
+        Priority::None => Priority::Asap,
+    }
+}
+
+// Converts an SGR parameter list from the theme (e.g. "1;97;41") into a
+// ratatui style, so the TUI honours --theme and --color like the other views.
+fn sgr_style(sgr: &str) -> Style {
+    const COLORS: [Color; 8] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+    ];
+    const BRIGHT: [Color; 8] = [
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+    let mut style = Style::default();
+    for code in sgr.split(';').filter_map(|c| c.parse::<usize>().ok()) {
+        style = match code {
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(COLORS[code - 30]),
+            40..=47 => style.bg(COLORS[code - 40]),
+            90..=97 => style.fg(BRIGHT[code - 90]),
+            100..=107 => style.bg(BRIGHT[code - 100]),
+            _ => style,
+        };
+    }
+    style
+}
+
+pub struct App<'a> {
+    manager: &'a mut TaskManager,
+    renderer: &'a Renderer,
+    selected: usize,
+    sort: SortKey,
+    reverse: bool,
+    filtering: bool,
+    filter_input: String,
+    filter: Option<Filter>,
+    message: String,
+    dirty: bool,
+    input_dates: DatePattern,
+    default_priority: Priority,
+}
+
+impl<'a> App<'a> {
+    pub fn new(manager: &'a mut TaskManager, renderer: &'a Renderer) -> Self {
+        App {
+            manager,
+            renderer,
+            selected: 0,
+            sort: SortKey::Due,
+            reverse: false,
+            filtering: false,
+            filter_input: String::new(),
+            filter: None,
+            message: String::new(),
+            dirty: false,
+            input_dates: DatePattern::default(),
+            default_priority: Priority::None,
+        }
+    }
+
+    // The open tasks matching the filter, in the current sort order.
+    pub fn visible(&self) -> Vec<&Task> {
+        let mut tasks = match &self.filter {
+            Some(filter) => filter.apply(self.manager.fetch_tasks(), self.renderer.today),
+            None => self.manager.fetch_tasks().iter().collect(),
+        };
+        sort_tasks(&mut tasks, self.sort, self.reverse);
+        tasks
+    }
+
+    fn selected_task(&self) -> Option<&Task> {
+        self.visible().get(self.selected).copied()
+    }
+
+    fn selected_id(&self) -> Option<String> {
+        self.selected_task().map(|t| t.id.clone())
+    }
+
+    fn select_id(&mut self, id: &str) {
+        if let Some(pos) = self.visible().iter().position(|t| t.id == id) {
+            self.selected = pos;
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.visible().len();
+        self.selected = self.selected.min(len.saturating_sub(1));
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.visible().len();
+        if len == 0 {
+            return;
+        }
+        self.selected = self.selected.saturating_add_signed(delta).min(len - 1);
+    }
+
+    // Returns whether the task list changed since the last call, so the
+    // caller knows to save.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = message.into();
+    }
+
+    fn update_filter(&mut self) {
+        self.selected = 0;
+        if self.filter_input.trim().is_empty() {
+            self.filter = None;
+            self.message.clear();
+            return;
+        }
+        // While a term is half typed the previous filter stays in effect.
+        match Filter::parse(&self.filter_input, &self.input_dates) {
+            Ok(filter) => {
+                self.filter = Some(filter);
+                self.message.clear();
+            }
+            Err(e) => self.message = e,
+        }
+    }
+
+    fn handle_filter_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.filtering = false,
+            KeyCode::Esc => {
+                self.filtering = false;
+                self.filter_input.clear();
+                self.update_filter();
+            }
+            KeyCode::Backspace => {
+                self.filter_input.pop();
+                self.update_filter();
+            }
+            KeyCode::Char(c) => {
+                self.filter_input.push(c);
+                self.update_filter();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Outcome {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Outcome::Quit;
+        }
+        if self.filtering {
+            self.handle_filter_key(key);
+            return Outcome::Continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Outcome::Quit,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(PAGE as isize),
+            KeyCode::PageUp => self.move_selection(-(PAGE as isize)),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => {
+                self.selected = self.visible().len().saturating_sub(1)
+            }
+            KeyCode::Char('d') => self.complete_selected(),
+            KeyCode::Char('x') | KeyCode::Delete => self.delete_selected(),
+            KeyCode::Char('p') => self.cycle_priority(),
+            KeyCode::Char('e') | KeyCode::Enter => {
+                if let Some(id) = self.selected_id() {
+                    return Outcome::OpenEditor(Some(id));
+                }
+            }
+            KeyCode::Char('n') => return Outcome::OpenEditor(None),
+            KeyCode::Char('/') => {
+                self.filtering = true;
+                self.message.clear();
+            }
+            KeyCode::Char('s') => {
+                self.sort = self.sort.next();
+                self.selected = 0;
+            }
+            KeyCode::Char('r') => {
+                self.reverse = !self.reverse;
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        Outcome::Continue
+    }
+
+    fn complete_selected(&mut self) {
+        let Some(id) = self.selected_id() else {
+            return;
+        };
+        match self.manager.complete_task_by_id(&id) {
+            Ok(Some(task)) => {
+                self.message = tf("completed", &[("description", &task.description)]);
+                self.dirty = true;
+            }
+            Ok(None) => {}
+            Err(e) => self.message = e,
+        }
+        self.clamp_selection();
+    }
+
+    fn delete_selected(&mut self) {
+        let Some(id) = self.selected_id() else {
+            return;
+        };
+        match self.manager.delete_task_by_id(&id) {
+            Ok(Some(task)) => {
+                self.message = tf(
+                    "moved_to_trash",
+                    &[("description", &task.description), ("id", &task.id)],
+                );
+                self.dirty = true;
+            }
+            Ok(None) => {}
+            Err(e) => self.message = e,
+        }
+        self.clamp_selection();
+    }
+
+    fn cycle_priority(&mut self) {
+        let Some(id) = self.selected_id() else {
+            return;
+        };
+        if let Some(mut task) = self.manager.find_task_by_id(&id).cloned() {
+            let update = FieldUpdate::Priority(next_priority(&task.priority));
+            apply_updates(&mut task, &[update]);
+            match self.manager.update_task(task) {
+                Ok(task) => {
+                    self.message = format!("{}: {}", t("field_priority"), task.priority);
+                    self.dirty = true;
+                }
+                Err(e) => self.message = e,
+            }
+        }
+        // Keep the same task selected when the sort order moves it.
+        self.select_id(&id);
+    }
+
+    // Applies the result of an editor session: updates to the task with `id`,
+    // or a new task when `id` is None.
+    pub fn apply_edit(&mut self, id: Option<&str>, updates: &[FieldUpdate]) {
+        let id = match id {
+            Some(id) => {
+                let Some(mut task) = self.manager.find_task_by_id(id).cloned() else {
+                    return;
+                };
+                if apply_updates(&mut task, updates) {
+                    if let Err(e) = self.manager.update_task(task) {
+                        self.message = e;
+                        return;
+                    }
+                    self.message = tf("tui_updated", &[("id", &id)]);
+                    self.dirty = true;
+                }
+                id.to_string()
+            }
+            None => {
+                let mut task = Task {
+                    priority: self.default_priority.clone(),
+                    ..Default::default()
+                };
+                for update in updates {
+                    update.apply(&mut task);
+                }
+                if task.description.is_empty() {
+                    self.message = t("description_empty").to_string();
+                    return;
+                }
+                match self.manager.create_task(task) {
+                    Ok(task) => {
+                        self.message = tf(
+                            "tui_created",
+                            &[("description", &task.description), ("id", &task.id)],
+                        );
+                        self.dirty = true;
+                        task.id.clone()
+                    }
+                    Err(e) => {
+                        self.message = e;
+                        return;
+                    }
+                }
+            }
+        };
+        self.select_id(&id);
+    }
+
+    fn style(&self, sgr: &str) -> Style {
+        if self.renderer.enabled {
+            sgr_style(sgr)
+        } else {
+            Style::default()
+        }
+    }
+
+    fn task_line(&self, task: &Task) -> Line<'static> {
+        let theme = &self.renderer.theme;
+        Line::from(vec![
+            Span::raw(format!("{:<4}", task.id)),
+            Span::styled(
+                format!("{:<11}", self.renderer.format_due(task)),
+                self.style(self.renderer.due_style(task)),
+            ),
+            Span::styled(
+                format!("{:<10}", task.priority.to_string()),
+                self.style(theme.priority_style(&task.priority)),
+            ),
+            Span::raw(task.description.clone()),
+        ])
+    }
+
+    fn detail_lines(&self, task: &Task) -> Vec<Line<'static>> {
+        let label = |name: &str| Span::styled(format!("{name:<13}"), Style::default().bold());
+        let timestamp = |at: Option<chrono::NaiveDateTime>| {
+            at.map(crate::dates::format_timestamp).unwrap_or_default()
+        };
+        let mut lines = vec![
+            Line::from(vec![label(t("field_id")), Span::raw(task.id.clone())]),
+            Line::from(vec![
+                label(t("field_description")),
+                Span::raw(task.description.clone()),
+            ]),
+            Line::from(vec![
+                label(t("field_due")),
+                Span::styled(
+                    self.renderer.format_due(task),
+                    self.style(self.renderer.due_style(task)),
+                ),
+            ]),
+            Line::from(vec![
+                label(t("field_priority")),
+                Span::styled(
+                    task.priority.to_string(),
+                    self.style(self.renderer.theme.priority_style(&task.priority)),
+                ),
+            ]),
+            Line::from(vec![
+                label(t("field_tags")),
+                Span::raw(task.tags.join(", ")),
+            ]),
+            Line::from(vec![
+                label(t("field_postponed")),
+                Span::raw(task.postpone_count().to_string()),
+            ]),
+            Line::from(vec![
+                label(t("field_created")),
+                Span::raw(timestamp(task.created_at())),
+            ]),
+            Line::from(vec![
+                label(t("field_modified")),
+                Span::raw(timestamp(task.modified_at())),
+            ]),
+            Line::default(),
+        ];
+        lines.extend(task.notes.lines().map(|l| Line::raw(l.to_string())));
+        lines
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let [main, status, keys] = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(main);
+
+        let visible = self.visible();
+        let title = tf(
+            if self.reverse {
+                "tui_title_reversed"
+            } else {
+                "tui_title"
+            },
+            &[("count", &visible.len()), ("sort", &self.sort.label())],
+        );
+        let title = format!(" {title} ");
+        let items: Vec<ListItem> = visible
+            .iter()
+            .map(|task| ListItem::new(self.task_line(task)))
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        let mut state = ListState::default();
+        if !visible.is_empty() {
+            state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, list_area, &mut state);
+
+        let details = match self.selected_task() {
+            Some(task) => self.detail_lines(task),
+            None => vec![Line::raw(t("no_tasks_found"))],
+        };
+        frame.render_widget(
+            Paragraph::new(details).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} ", t("tui_details"))),
+            ),
+            detail_area,
+        );
+
+        let status_line = if self.filtering {
+            let filter = tf("tui_filter", &[("filter", &self.filter_input)]);
+            format!("{filter}_  {}", self.message)
+        } else if !self.message.is_empty() {
+            self.message.clone()
+        } else if !self.filter_input.is_empty() {
+            tf("tui_filter", &[("filter", &self.filter_input)])
+        } else {
+            String::new()
+        };
+        frame.render_widget(Paragraph::new(status_line), status);
+        frame.render_widget(
+            Paragraph::new(t("tui_keys")).style(Style::default().add_modifier(Modifier::DIM)),
+            keys,
+        );
+    }
+}
+
+pub fn run(manager: &mut TaskManager, renderer: &Renderer, config: &Config) -> Result<(), String> {
+    if !std::io::stdout().is_terminal() || !std::io::stdin().is_terminal() {
+        return Err(t("tui_needs_terminal").to_string());
+    }
+    let mut app = App::new(manager, renderer);
+    app.input_dates = config.input_format();
+    app.default_priority = config.default_priority();
+    if let Some(sort) = config.view_sort() {
+        app.sort = sort;
+    }
+    if let Some(filter) = config.view_filter() {
+        app.filter_input = filter.to_string();
+        app.update_filter();
+    }
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app, config.database());
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App, filename: &str) -> Result<(), String> {
+    loop {
+        terminal
+            .draw(|frame| app.draw(frame))
+            .map_err(|e| e.to_string())?;
+        let Event::Key(key) = event::read().map_err(|e| e.to_string())? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match app.handle_key(key) {
+            Outcome::Quit => return Ok(()),
+            Outcome::Continue => {}
+            Outcome::OpenEditor(id) => {
+                let current = id
+                    .as_deref()
+                    .and_then(|id| app.manager.find_task_by_id(id))
+                    .cloned()
+                    .unwrap_or_else(|| Task {
+                        priority: app.default_priority.clone(),
+                        ..Default::default()
+                    });
+                ratatui::restore();
+                let edited = editor::edit_task(&current, &app.input_dates);
+                *terminal = ratatui::init();
+                match edited {
+                    Ok(Some(updates)) => app.apply_edit(id.as_deref(), &updates),
+                    Ok(None) => app.set_message(t("cancelled")),
+                    Err(e) => app.set_message(e),
+                }
+            }
+        }
+
+        if app.take_dirty() {
+            if let Err(e) = app.manager.save_tasks(filename) {
+                app.set_message(tf("error_saving", &[("error", &e)]));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn renderer() -> Renderer {
+        Renderer {
+            today: NaiveDate::from_ymd_opt(2026, 10, 21).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    fn manager() -> TaskManager {
+        let task = |id: &str, description: &str, due_date: &str, priority, tags: &[&str]| Task {
+            id: id.to_string(),
+            description: description.to_string(),
+            due_date: due_date.to_string(),
+            priority,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+        let mut manager = TaskManager::new();
+        manager.set_tasks(vec![
+            task(
+                "1A",
+                "Write report",
+                "10/25/2026",
+                Priority::Minor,
+                &["work"],
+            ),
+            task("2B", "Pay rent", "10/20/2026", Priority::Asap, &["home"]),
+            task("3C", "Call plumber", "", Priority::Medium, &["home"]),
+        ]);
+        manager
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    fn ids(app: &App) -> Vec<String> {
+        app.visible().iter().map(|t| t.id.clone()).collect()
+    }
+
+    fn screen(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 16)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn renders_list_and_details_headlessly() {
+        let mut manager = manager();
+        let renderer = renderer();
+        let app = App::new(&mut manager, &renderer);
+        let screen = screen(&app);
+        assert!(screen.contains("Tasks (3) sorted by due"));
+        assert!(screen.contains("> 2B  10/20/2026 ASAP      Pay rent"));
+        assert!(screen.contains("Details"));
+        assert!(screen.contains("q quit"));
+    }
+
+    #[test]
+    fn sort_toggles_and_reverse() {
+        let mut manager = manager();
+        let renderer = renderer();
+        let mut app = App::new(&mut manager, &renderer);
+        assert_eq!(ids(&app), vec!["2B", "1A", "3C"]);
+        press(&mut app, "s");
+        assert_eq!(ids(&app), vec!["2B", "3C", "1A"]);
+        press(&mut app, "s");
+        assert_eq!(ids(&app), vec!["3C", "2B", "1A"]);
+        press(&mut app, "r");
+        assert_eq!(ids(&app), vec!["1A", "2B", "3C"]);
+    }
+
+    #[test]
+    fn live_filter_narrows_the_list() {
+        let mut manager = manager();
+        let renderer = renderer();
+        let mut app = App::new(&mut manager, &renderer);
+        press(&mut app, "/tag:home");
+        assert_eq!(ids(&app), vec!["2B", "3C"]);
+        press(&mut app, " -due:none");
+        assert_eq!(ids(&app), vec!["2B"]);
+        assert!(screen(&app).contains("Filter: tag:home -due:none_"));
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(ids(&app).len(), 3);
+        assert!(!app.take_dirty());
+    }
+
+    #[test]
+    fn shortcuts_change_tasks() {
+        let mut manager = manager();
+        let renderer = renderer();
+        let mut app = App::new(&mut manager, &renderer);
+        press(&mut app, "jp");
+        assert_eq!(app.selected_task().unwrap().priority, Priority::None);
+        assert!(app.take_dirty());
+        press(&mut app, "d");
+        assert_eq!(ids(&app), vec!["2B", "3C"]);
+        press(&mut app, "Gx");
+        assert_eq!(ids(&app), vec!["2B"]);
+        assert!(app.take_dirty());
+        assert_eq!(manager.fetch_archive()[0].id, "1A");
+        assert_eq!(manager.fetch_trash()[0].id, "3C");
+    }
+
+    #[test]
+    fn editor_results_are_applied() {
+        let mut manager = manager();
+        let renderer = renderer();
+        let mut app = App::new(&mut manager, &renderer);
+        assert_eq!(
+            app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE)),
+            Outcome::OpenEditor(None)
+        );
+        let updates =
+            vec![FieldUpdate::parse("description=Buy milk", &DatePattern::default()).unwrap()];
+        app.apply_edit(None, &updates);
+        assert_eq!(app.selected_task().unwrap().description, "Buy milk");
+        assert!(app.take_dirty());
+        app.apply_edit(
+            Some("1A"),
+            &[FieldUpdate::parse("due=", &DatePattern::default()).unwrap()],
+        );
+        assert_eq!(app.selected_task().unwrap().id, "1A");
+        assert_eq!(app.selected_task().unwrap().due_date, "");
+    }
+
+    #[test]
+    fn theme_codes_map_to_styles() {
+        let style = sgr_style("1;97;41");
+        assert_eq!(style.fg, Some(Color::White));
+        assert_eq!(style.bg, Some(Color::Red));
+        assert!(style.add_modifier.contains(Modifier::BOLD));
+    }
+}
@@ -0,0 +1,267 @@
+use crate::models::{Priority, Task};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+// Coefficients of the urgency model, from the `[urgency]` config table. Each
+// term of the score is a factor between 0 and 1 times its weight.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Weights {
+    pub priority: f64,
+    pub due: f64,
+    pub age: f64,
+    pub tags: f64,
+    pub tag: Vec<(String, f64)>,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights {
+            priority: 6.0,
+            due: 12.0,
+            age: 2.0,
+            tags: 1.0,
+            tag: Vec::new(),
+        }
+    }
+}
+
+// What `explain` prints in the JSON formats.
+#[derive(Serialize)]
+pub struct Explanation<'a> {
+    pub id: &'a str,
+    pub urgency: f64,
+    pub terms: Vec<Term>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct Term {
+    pub term: String,
+    pub detail: String,
+    pub factor: f64,
+    pub weight: f64,
+    pub points: f64,
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+impl Term {
+    // Points come from the rounded factor, so every row of `explain` adds up
+    // as printed.
+    fn new(term: &str, detail: String, factor: f64, weight: f64) -> Term {
+        let factor = round(factor);
+        Term {
+            term: term.to_string(),
+            detail,
+            factor,
+            weight,
+            points: round(factor * weight),
+        }
+    }
+}
+
+fn priority_factor(priority: &Priority) -> f64 {
+    match priority {
+        Priority::Asap => 1.0,
+        Priority::Important => 0.65,
+        Priority::Medium => 0.3,
+        Priority::Minor => 0.1,
+        Priority::None => 0.0,
+    }
+}
+
+// Full weight from a week overdue, falling to a fifth of it for tasks due
+// two weeks or more from now.
+fn due_factor(days_left: i64) -> f64 {
+    match days_left {
+        ..=-7 => 1.0,
+        14.. => 0.2,
+        days => 1.0 - (days + 7) as f64 * 0.8 / 21.0,
+    }
+}
+
+fn tags_factor(count: usize) -> f64 {
+    match count {
+        0 => 0.0,
+        1 => 0.8,
+        2 => 0.9,
+        _ => 1.0,
+    }
+}
+
+impl Weights {
+    // The score broken down term by term, as `explain` shows it. Weighted
+    // tags the task does not have are left out.
+    pub fn terms(&self, task: &Task, today: NaiveDate) -> Vec<Term> {
+        let mut terms = vec![Term::new(
+            "priority",
+            task.priority.name().to_lowercase(),
+            priority_factor(&task.priority),
+            self.priority,
+        )];
+
+        let days_left = task.due().map(|due| (due - today).num_days());
+        terms.push(Term::new(
+            "due",
+            days_left.map(|d| format!("{d:+}d")).unwrap_or_default(),
+            days_left.map(due_factor).unwrap_or(0.0),
+            self.due,
+        ));
+
+        // A year old counts fully.
+        let age = task
+            .created_at()
+            .map(|at| (today - at.date()).num_days().max(0));
+        terms.push(Term::new(
+            "age",
+            age.map(|d| format!("{d}d")).unwrap_or_default(),
+            age.map(|d| (d as f64 / 365.0).min(1.0)).unwrap_or(0.0),
+            self.age,
+        ));
+
+        terms.push(Term::new(
+            "tags",
+            task.tags.len().to_string(),
+            tags_factor(task.tags.len()),
+            self.tags,
+        ));
+        for (tag, weight) in &self.tag {
+            if task.tags.iter().any(|t| t.to_lowercase() == *tag) {
+                terms.push(Term::new(
+                    &format!("tag:{tag}"),
+                    String::new(),
+                    1.0,
+                    *weight,
+                ));
+            }
+        }
+        terms
+    }
+
+    pub fn score(&self, task: &Task, today: NaiveDate) -> f64 {
+        round(self.terms(task, today).iter().map(|t| t.points).sum())
+    }
+}
+
+// Like the message language, the weights are set once at startup so sorting
+// can use them wherever it happens.
+static WEIGHTS: OnceLock<Weights> = OnceLock::new();
+
+pub fn set_weights(weights: Weights) {
+    let _ = WEIGHTS.set(weights);
+}
+
+pub fn weights() -> &'static Weights {
+    WEIGHTS.get_or_init(Weights::default)
+}
+
+pub fn score(task: &Task, today: NaiveDate) -> f64 {
+    weights().score(task, today)
+}
+
+// The most urgent task; ties go to the task listed first.
+pub fn most_urgent<'a>(tasks: &[&'a Task], today: NaiveDate) -> Option<&'a Task> {
+    tasks
+        .iter()
+        .copied()
+        .fold(None, |best: Option<(&Task, f64)>, task| {
+            let score = score(task, today);
+            match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((task, score)),
+            }
+        })
+        .map(|(task, _)| task)
+}
+
+// One aligned row per term: name, detail, and how the points come about.
+pub fn render_terms(terms: &[Term]) -> String {
+    let name_width = terms.iter().map(|t| t.term.len()).max().unwrap_or(0);
+    let detail_width = terms.iter().map(|t| t.detail.len()).max().unwrap_or(0);
+    terms
+        .iter()
+        .map(|t| {
+            format!(
+                "  {:<name_width$}  {:<detail_width$}  {:.2} × {:>5.2} = {:>6.2}",
+                t.term, t.detail, t.factor, t.weight, t.points
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Action;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+    }
+
+    fn task(priority: Priority, due_date: &str, tags: &[&str]) -> Task {
+        Task {
+            id: "1A".to_string(),
+            description: "Call the vendor".to_string(),
+            due_date: due_date.to_string(),
+            priority,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn due_factor_rises_towards_overdue() {
+        assert_eq!(due_factor(-30), 1.0);
+        assert_eq!(due_factor(-7), 1.0);
+        assert_eq!(round(due_factor(0)), 0.73);
+        assert_eq!(due_factor(14), 0.2);
+        assert_eq!(due_factor(100), 0.2);
+    }
+
+    #[test]
+    fn terms_explain_the_score() {
+        let weights = Weights {
+            tag: vec![("next".to_string(), 15.0), ("someday".to_string(), -5.0)],
+            ..Default::default()
+        };
+        let mut task = task(Priority::Important, "2026-10-14", &["next", "work"]);
+        task.history.push(crate::models::HistoryEntry {
+            at: NaiveDate::from_ymd_opt(2026, 4, 24)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+            action: Action::Created,
+            detail: String::new(),
+        });
+
+        let terms = weights.terms(&task, today());
+        let summary: Vec<(&str, &str, f64)> = terms
+            .iter()
+            .map(|t| (t.term.as_str(), t.detail.as_str(), t.points))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("priority", "important", 3.9),
+                ("due", "-7d", 12.0),
+                ("age", "180d", 0.98),
+                ("tags", "2", 0.9),
+                ("tag:next", "", 15.0),
+            ]
+        );
+        assert_eq!(weights.score(&task, today()), 32.78);
+    }
+
+    #[test]
+    fn most_urgent_prefers_higher_scores() {
+        let later = task(Priority::Asap, "2026-12-01", &[]);
+        let overdue = task(Priority::Minor, "2026-10-01", &[]);
+        let undated = task(Priority::None, "", &[]);
+        let tasks = vec![&later, &undated, &overdue];
+        assert_eq!(most_urgent(&tasks, today()), Some(&overdue));
+        assert_eq!(most_urgent(&[], today()), None);
+    }
+}
@@ -4,6 +4,7 @@ use std::io::BufReader;
 use xml::reader::{EventReader, XmlEvent};
 use xml::writer::{EmitterConfig, XmlEvent as XmlWriteEvent};
 
+use crate::i18n::tf;
 use crate::models::{Priority, Task};
 
 pub fn read(filename: &str) -> Result<Vec<Task>, Box<dyn Error>> {
@@ -25,6 +26,7 @@ pub fn read(filename: &str) -> Result<Vec<Task>, Box<dyn Error>> {
                         due_date: String::new(),
                         priority: Priority::None,
                         notes: String::new(),
+                        ..Default::default()
                     });
                 }
                 other => {
@@ -48,6 +50,19 @@ pub fn read(filename: &str) -> Result<Vec<Task>, Box<dyn Error>> {
                         "Due_Date" => task.due_date = text,
                         "Priority" => task.priority = text.parse().unwrap(),
                         "Notes" => task.notes = text,
+                        "Tags" => task.tags = Task::parse_tags(&text),
+                        "Status" => task.status = text.parse().unwrap(),
+                        "Rank" => task.rank = text,
+                        "Remind" => task.remind = text,
+                        "Entry" => match text.parse() {
+                            Ok(entry) => task.history.push(entry),
+                            // A bad entry must not make the whole database
+                            // unreadable.
+                            Err(e) => eprintln!(
+                                "{}",
+                                tf("warning_bad_history", &[("id", &task.id), ("error", &e)])
+                            ),
+                        },
                         _ => {}
                     }
                 }
@@ -86,8 +101,20 @@ pub fn write(filename: &str, tasks: &[Task]) -> Result<(), Box<dyn Error>> {
         write_field("ID", &task.id)?;
         write_field("Description", &task.description)?;
         write_field("Due_Date", &task.due_date)?;
-        write_field("Priority", &task.priority.to_string())?;
+        write_field("Priority", task.priority.name())?;
         write_field("Notes", &task.notes)?;
+        write_field("Tags", &task.tags.join(","))?;
+        write_field("Status", &task.status.to_string())?;
+        write_field("Rank", &task.rank)?;
+        write_field("Remind", &task.remind)?;
+
+        writer.write(XmlWriteEvent::start_element("History"))?;
+        for entry in &task.history {
+            writer.write(XmlWriteEvent::start_element("Entry"))?;
+            writer.write(XmlWriteEvent::characters(&entry.to_string()))?;
+            writer.write(XmlWriteEvent::end_element())?;
+        }
+        writer.write(XmlWriteEvent::end_element())?;
 
         writer.write(XmlWriteEvent::end_element())?;
     }
@@ -98,6 +125,7 @@ pub fn write(filename: &str, tasks: &[Task]) -> Result<(), Box<dyn Error>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::Status;
     use std::io::Read;
 
     const XML_TEST_FILE_PATH: &str = "xml_test_files/";
@@ -111,6 +139,7 @@ mod tests {
                 due_date: "1/25/2023".to_string(),
                 priority: Priority::Asap,
                 notes: "Some notes here".to_string(),
+                ..Default::default()
             },
             Task {
                 id: "2B".to_string(),
@@ -118,6 +147,7 @@ mod tests {
                 due_date: "3/10/2023".to_string(),
                 priority: Priority::Medium,
                 notes: "".to_string(),
+                ..Default::default()
             },
             Task {
                 id: "3C".to_string(),
@@ -125,6 +155,7 @@ mod tests {
                 due_date: "5/31/2023".to_string(),
                 priority: Priority::None,
                 notes: "".to_string(),
+                ..Default::default()
             },
         ];
 
@@ -142,6 +173,7 @@ mod tests {
             due_date: " 1/25/2023 ".to_string(),
             priority: Priority::None,
             notes: "".to_string(),
+            ..Default::default()
         }];
 
         let filename = XML_TEST_FILE_PATH.to_owned() + "invalid_test_tasks.xml";
@@ -158,6 +190,7 @@ mod tests {
             due_date: "01/01/2025".to_string(),
             priority: Priority::None,
             notes: "".to_string(),
+            ..Default::default()
         }];
 
         let filename = XML_TEST_FILE_PATH.to_owned() + "legacy_tasks.xml";
@@ -166,6 +199,19 @@ mod tests {
         assert_eq!(expected_tasks, result_tasks);
     }
 
+    #[test]
+    fn read_skips_bad_history_entries() {
+        let filename = XML_TEST_FILE_PATH.to_owned() + "bad_history_tasks.xml";
+        let result_tasks = read(&filename).unwrap();
+
+        assert_eq!(result_tasks.len(), 1);
+        assert_eq!(result_tasks[0].description, "Task with a damaged history");
+        assert_eq!(
+            result_tasks[0].history,
+            vec!["2023-06-01T09:00:00 created".parse().unwrap()]
+        );
+    }
+
     #[test]
     fn read_empty_xml_file() {
         let filename = XML_TEST_FILE_PATH.to_owned() + "empty_file.xml";
@@ -185,6 +231,7 @@ mod tests {
                 due_date: "2023-06-10".to_string(),
                 priority: Priority::None,
                 notes: "".to_string(),
+                ..Default::default()
             },
             Task {
                 id: "B2".to_string(),
@@ -192,6 +239,14 @@ mod tests {
                 due_date: "2023-06-15".to_string(),
                 priority: Priority::Important,
                 notes: "some notes".to_string(),
+                tags: vec!["home".to_string(), "errands".to_string()],
+                status: Status::Done,
+                rank: "i".to_string(),
+                remind: "-1h".to_string(),
+                history: vec![
+                    "2023-06-01T09:00:00 created".parse().unwrap(),
+                    "2023-06-14T17:30:00 completed".parse().unwrap(),
+                ],
             },
         ];
 
@@ -209,6 +264,11 @@ mod tests {
   <Due_Date>2023-06-10</Due_Date>
   <Priority>None</Priority>
   <Notes />
+  <Tags />
+  <Status>Open</Status>
+  <Rank />
+  <Remind />
+  <History />
 </Task>
 <Task>
   <ID>B2</ID>
@@ -216,6 +276,14 @@ mod tests {
   <Due_Date>2023-06-15</Due_Date>
   <Priority>Important</Priority>
   <Notes>some notes</Notes>
+  <Tags>home,errands</Tags>
+  <Status>Done</Status>
+  <Rank>i</Rank>
+  <Remind>-1h</Remind>
+  <History>
+    <Entry>2023-06-01T09:00:00 created</Entry>
+    <Entry>2023-06-14T17:30:00 completed</Entry>
+  </History>
 </Task>"#;
 
         assert_eq!(contents, expected_xml);
@@ -236,6 +304,10 @@ mod tests {
                 due_date: "2024-01-15".to_string(),
                 priority: Priority::Asap,
                 notes: "Don't forget milk".to_string(),
+                rank: "i".to_string(),
+                remind: "-1h".to_string(),
+                history: vec!["2024-01-10T08:15:00 created".parse().unwrap()],
+                ..Default::default()
             },
             Task {
                 id: "BB".to_string(),
@@ -243,6 +315,7 @@ mod tests {
                 due_date: "2024-01-16".to_string(),
                 priority: Priority::None,
                 notes: "".to_string(),
+                ..Default::default()
             },
         ];
 
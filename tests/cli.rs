@@ -0,0 +1,65 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+fn work_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("todo_cli_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(dir: &Path, args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_todo_cli"))
+        .args(args)
+        .current_dir(dir)
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("HOME", dir)
+        .env("LANG", "C")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn parse(stdout: &str) -> serde_json::Value {
+    serde_json::from_str(stdout).unwrap_or_else(|e| panic!("{e}: {stdout}"))
+}
+
+#[test]
+fn prompts_stay_out_of_json_output() {
+    let dir = work_dir("json_prompts");
+    let mut ids = Vec::new();
+    for description in ["Buy milk", "Call mom", "Pay rent"] {
+        let input = format!("{description}\n12/24/2030\n\n\n\n");
+        let created = parse(&run(&dir, &["new", "--format", "json"], &input));
+        assert_eq!(created[0]["description"], description);
+        ids.push(created[0]["id"].as_str().unwrap().to_string());
+    }
+
+    let input = format!("{}\n", ids[0]);
+    let completed = parse(&run(&dir, &["done", "--format", "json"], &input));
+    assert_eq!(completed[0]["id"].as_str(), Some(ids[0].as_str()));
+
+    let args = ["delete", &ids[1], &ids[2], "--format", "json"];
+    let deleted = parse(&run(&dir, &args, "y\n"));
+    assert_eq!(deleted.as_array().map(Vec::len), Some(2));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}